@@ -44,10 +44,10 @@ fn main() -> std::io::Result<()> {
     };
 
     let booster = Booster::train(train_dataset, &params).unwrap();
-    let result = booster.predict(test_features).unwrap();
+    let result = booster.predict_mat(test_features).unwrap();
 
     let mut tp = 0;
-    for (label, pred) in zip(&test_labels, &result[0]) {
+    for (label, pred) in zip(&test_labels, result.as_slice()) {
         if (*label == 1_f32 && *pred > 0.5_f64) || (*label == 0_f32 && *pred <= 0.5_f64) {
             tp += 1;
         }
@@ -59,6 +59,6 @@ fn main() -> std::io::Result<()> {
     for (feature, importance) in zip(&feature_name, &feature_importance) {
         println!("{}: {}", feature, importance);
     }
-    println!("result: {} / {}", &tp, result[0].len());
+    println!("result: {} / {}", &tp, result.num_rows());
     Ok(())
 }
@@ -64,16 +64,16 @@ fn main() -> std::io::Result<()> {
     };
 
     let booster = Booster::train(train_dataset, &params).unwrap();
-    let result = booster.predict(test_features).unwrap();
+    let result = booster.predict_mat(test_features).unwrap();
 
     let mut tp = 0;
-    for (label, pred) in zip(&test_labels, &result) {
-        let argmax_pred = argmax(&pred);
+    for (label, pred) in zip(&test_labels, result.rows()) {
+        let argmax_pred = argmax(pred);
         if *label == argmax_pred as f32 {
             tp += 1;
         }
         println!("{}, {}, {:?}", label, argmax_pred, &pred);
     }
-    println!("{} / {}", &tp, result.len());
+    println!("{} / {}", &tp, result.num_rows());
     Ok(())
 }
@@ -44,15 +44,15 @@ fn main() -> std::io::Result<()> {
     };
 
     let booster = Booster::train(train_dataset, &params).unwrap();
-    let result = booster.predict(test_features).unwrap();
+    let result = booster.predict_mat(test_features).unwrap();
 
     let mut tp = 0;
-    for (label, pred) in zip(&test_labels, &result[0]) {
+    for (label, pred) in zip(&test_labels, result.as_slice()) {
         if (*label == 1_f32 && *pred > 0.5_f64) || (*label == 0_f32 && *pred <= 0.5_f64) {
             tp += 1;
         }
         println!("{}, {}", label, pred)
     }
-    println!("{} / {}", &tp, result[0].len());
+    println!("{} / {}", &tp, result.num_rows());
     Ok(())
 }
@@ -1,5 +1,8 @@
+#[cfg(feature = "bindgen")]
 extern crate bindgen;
 extern crate cmake;
+#[cfg(feature = "system")]
+extern crate pkg_config;
 
 use cmake::Config;
 use std::env;
@@ -8,14 +11,75 @@ use std::process::Command;
 
 fn main() {
     let target = env::var("TARGET").unwrap();
-    let out_dir = env::var("OUT_DIR").unwrap();
-    let lgbm_root = Path::new(&out_dir).join("lightgbm");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    for var in &[
+        "LIGHTGBM_LIB_DIR",
+        "LIGHTGBM_INCLUDE_DIR",
+        "LIGHTGBM_STATIC",
+        "LIGHTGBM_SANITIZERS",
+    ] {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    let link_static = env::var("LIGHTGBM_STATIC").is_ok_and(|v| v != "0");
+    let mut include_dirs = env::var("LIGHTGBM_INCLUDE_DIR")
+        .map(PathBuf::from)
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    // An explicit library directory takes precedence over pkg-config and the vendored sources.
+    if let Ok(lib_dir) = env::var("LIGHTGBM_LIB_DIR") {
+        link_prebuilt(&target, &lib_dir, link_static);
+    } else if let Some(dirs) = probe_system(&target, link_static) {
+        include_dirs.extend(dirs);
+    } else {
+        let lgbm_root = build_vendored(&target, &out_dir);
+        include_dirs.push(lgbm_root.join("include"));
+    }
+
+    generate_bindings(&include_dirs, &out_dir);
+}
+
+/// Link the library in `lib_dir`, dynamically unless `link_static`.
+fn link_prebuilt(target: &str, lib_dir: &str, link_static: bool) {
+    println!("cargo:rustc-link-search=native={}", lib_dir);
+    let kind = if link_static { "static" } else { "dylib" };
+    if target.contains("windows") {
+        println!("cargo:rustc-link-lib={}=lib_lightgbm", kind);
+    } else {
+        println!("cargo:rustc-link-lib={}=_lightgbm", kind);
+    }
+    if link_static {
+        link_cxx_runtime(target);
+    }
+}
+
+/// Find the library with pkg-config, returning its include directories.
+#[cfg(feature = "system")]
+fn probe_system(target: &str, link_static: bool) -> Option<Vec<PathBuf>> {
+    let library = pkg_config::Config::new()
+        .statik(link_static)
+        .probe("lightgbm")
+        .unwrap_or_else(|e| panic!("Failed to find LightGBM with pkg-config: {}", e));
+    if link_static {
+        link_cxx_runtime(target);
+    }
+    Some(library.include_paths)
+}
+
+#[cfg(not(feature = "system"))]
+fn probe_system(_target: &str, _link_static: bool) -> Option<Vec<PathBuf>> {
+    None
+}
+
+/// Build the vendored sources as a static library, returning the copied source root.
+fn build_vendored(target: &str, out_dir: &Path) -> PathBuf {
+    let lgbm_root = out_dir.join("lightgbm");
 
     // copy source code
     if !lgbm_root.exists() {
         let status = if target.contains("windows") {
             Command::new("cmd")
-                .args(&[
+                .args([
                     "/C",
                     "echo D | xcopy /S /Y lightgbm",
                     lgbm_root.to_str().unwrap(),
@@ -23,7 +87,7 @@ fn main() {
                 .status()
         } else {
             Command::new("cp")
-                .args(&["-r", "lightgbm", lgbm_root.to_str().unwrap()])
+                .args(["-r", "lightgbm", lgbm_root.to_str().unwrap()])
                 .status()
         };
         if let Some(err) = status.err() {
@@ -36,38 +100,137 @@ fn main() {
     }
 
     // CMake
-    let dst = Config::new(&lgbm_root)
-        .profile("Release")
-        .uses_cxx11()
+    let mut config = Config::new(&lgbm_root);
+    config
+        .profile(if cfg!(feature = "debug") {
+            "Debug"
+        } else {
+            "Release"
+        })
         .define("BUILD_STATIC_LIB", "ON")
-        .build();
+        .define("USE_OPENMP", on_off(cfg!(feature = "openmp")))
+        .define("USE_DEBUG", on_off(cfg!(feature = "debug")));
+    let sanitizers = sanitizers();
+    if !sanitizers.is_empty() {
+        config
+            .define("USE_SANITIZER", "ON")
+            .define("ENABLED_SANITIZERS", sanitizers.join(";"));
+        link_sanitizer_runtimes(target, &sanitizers);
+    }
+    let dst = config.build();
 
-    // bindgen build
-    let bindings = bindgen::Builder::default()
-        .header("wrapper.h")
-        .clang_args(&["-x", "c++", "-std=c++11"])
-        .clang_arg(format!("-I{}", lgbm_root.join("include").display()))
-        .generate()
-        .expect("Unable to generate bindings");
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings.");
+    link_cxx_runtime(target);
 
-    // link to appropriate C++ lib
+    println!("cargo:rustc-link-search={}", out_dir.join("lib").display());
+    println!("cargo:rustc-link-search=native={}", dst.display());
+    if target.contains("windows") {
+        println!("cargo:rustc-link-lib=static=lib_lightgbm");
+    } else {
+        println!("cargo:rustc-link-lib=static=_lightgbm");
+    }
+    lgbm_root
+}
+
+/// Link the C++ standard library and OpenMP, which a static LightGBM depends on.
+///
+/// With the `static-runtime` feature, both are linked statically on Linux, e.g. for fully
+/// static musl binaries.
+fn link_cxx_runtime(target: &str) {
+    let openmp = cfg!(feature = "openmp");
     if target.contains("apple") {
         println!("cargo:rustc-link-lib=c++");
-        println!("cargo:rustc-link-lib=dylib=omp");
+        if openmp {
+            println!("cargo:rustc-link-lib=dylib=omp");
+        }
     } else if target.contains("linux") {
-        println!("cargo:rustc-link-lib=stdc++");
-        println!("cargo:rustc-link-lib=dylib=gomp");
+        let kind = if cfg!(feature = "static-runtime") {
+            "static"
+        } else {
+            "dylib"
+        };
+        println!("cargo:rustc-link-lib={}=stdc++", kind);
+        if openmp {
+            println!("cargo:rustc-link-lib={}=gomp", kind);
+        }
     }
+}
 
-    println!("cargo:rustc-link-search={}", out_path.join("lib").display());
-    println!("cargo:rustc-link-search=native={}", dst.display());
-    if target.contains("windows") {
-        println!("cargo:rustc-link-lib=static=lib_lightgbm");
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "ON"
     } else {
-        println!("cargo:rustc-link-lib=static=_lightgbm");
+        "OFF"
+    }
+}
+
+/// Sanitizers to build LightGBM with: none without the `sanitizer` feature, otherwise those in
+/// the comma-separated `LIGHTGBM_SANITIZERS` or LightGBM's default of address, leak and
+/// undefined behavior.
+fn sanitizers() -> Vec<String> {
+    if !cfg!(feature = "sanitizer") {
+        return Vec::new();
+    }
+    match env::var("LIGHTGBM_SANITIZERS") {
+        Ok(value) => value
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Err(_) => vec![
+            "address".to_string(),
+            "leak".to_string(),
+            "undefined".to_string(),
+        ],
+    }
+}
+
+/// Link the runtimes of the sanitizers that LightGBM is instrumented with, unless Rust code is
+/// built with `-Zsanitizer`, which brings its own runtimes.
+///
+/// The address sanitizer runtime includes the leak sanitizer, so lsan is only linked on its own.
+fn link_sanitizer_runtimes(target: &str, sanitizers: &[String]) {
+    let rustflags = env::var("CARGO_ENCODED_RUSTFLAGS").unwrap_or_default();
+    if rustflags.contains("sanitizer=") || !target.contains("linux") {
+        return;
+    }
+    let address = sanitizers.iter().any(|s| s == "address");
+    for sanitizer in sanitizers {
+        let runtime = match sanitizer.as_str() {
+            "address" => "asan",
+            "leak" if address => continue,
+            "leak" => "lsan",
+            "thread" => "tsan",
+            "undefined" => "ubsan",
+            _ => continue,
+        };
+        println!("cargo:rustc-link-lib=dylib={}", runtime);
     }
 }
+
+/// Generate bindings from `LightGBM/c_api.h` in `include_dirs` or the default include path.
+///
+/// `size_t` is mapped to `usize`, which keeps the pre-generated `src/bindings.rs` valid on
+/// every platform.
+#[cfg(feature = "bindgen")]
+fn generate_bindings(include_dirs: &[PathBuf], out_dir: &Path) {
+    let bindings = include_dirs
+        .iter()
+        .fold(
+            bindgen::Builder::default()
+                .header("wrapper.h")
+                .clang_args(&["-x", "c++", "-std=c++11"])
+                .whitelist_function("LGBM_.*")
+                .whitelist_var("C_API_.*")
+                .size_t_is_usize(true),
+            |builder, dir| builder.clang_arg(format!("-I{}", dir.display())),
+        )
+        .generate()
+        .expect("Unable to generate bindings");
+    bindings
+        .write_to_file(out_dir.join("bindings.rs"))
+        .expect("Couldn't write bindings.");
+}
+
+/// Without the `bindgen` feature, the bindings in `src/bindings.rs` are used.
+#[cfg(not(feature = "bindgen"))]
+fn generate_bindings(_include_dirs: &[PathBuf], _out_dir: &Path) {}
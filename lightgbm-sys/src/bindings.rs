@@ -0,0 +1,681 @@
+/* automatically generated by rust-bindgen 0.56.0 */
+
+pub const C_API_DTYPE_FLOAT32: u32 = 0;
+pub const C_API_DTYPE_FLOAT64: u32 = 1;
+pub const C_API_DTYPE_INT32: u32 = 2;
+pub const C_API_DTYPE_INT64: u32 = 3;
+pub const C_API_PREDICT_NORMAL: u32 = 0;
+pub const C_API_PREDICT_RAW_SCORE: u32 = 1;
+pub const C_API_PREDICT_LEAF_INDEX: u32 = 2;
+pub const C_API_PREDICT_CONTRIB: u32 = 3;
+pub const C_API_MATRIX_TYPE_CSR: u32 = 0;
+pub const C_API_MATRIX_TYPE_CSC: u32 = 1;
+pub const C_API_FEATURE_IMPORTANCE_SPLIT: u32 = 0;
+pub const C_API_FEATURE_IMPORTANCE_GAIN: u32 = 1;
+pub type DatasetHandle = *mut ::std::os::raw::c_void;
+pub type BoosterHandle = *mut ::std::os::raw::c_void;
+pub type FastConfigHandle = *mut ::std::os::raw::c_void;
+extern "C" {
+    pub fn LGBM_GetLastError() -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn LGBM_RegisterLogCallback(
+        callback: ::std::option::Option<unsafe extern "C" fn(arg1: *const ::std::os::raw::c_char)>,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromFile(
+        filename: *const ::std::os::raw::c_char,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromSampledColumn(
+        sample_data: *mut *mut f64,
+        sample_indices: *mut *mut ::std::os::raw::c_int,
+        ncol: i32,
+        num_per_col: *const ::std::os::raw::c_int,
+        num_sample_row: i32,
+        num_total_row: i32,
+        parameters: *const ::std::os::raw::c_char,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateByReference(
+        reference: DatasetHandle,
+        num_total_row: i64,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetPushRows(
+        dataset: DatasetHandle,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nrow: i32,
+        ncol: i32,
+        start_row: i32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetPushRowsByCSR(
+        dataset: DatasetHandle,
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nindptr: i64,
+        nelem: i64,
+        num_col: i64,
+        start_row: i64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromCSR(
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nindptr: i64,
+        nelem: i64,
+        num_col: i64,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromCSRFunc(
+        get_row_funptr: *mut ::std::os::raw::c_void,
+        num_rows: ::std::os::raw::c_int,
+        num_col: i64,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromCSC(
+        col_ptr: *const ::std::os::raw::c_void,
+        col_ptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        ncol_ptr: i64,
+        nelem: i64,
+        num_row: i64,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromMat(
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nrow: i32,
+        ncol: i32,
+        is_row_major: ::std::os::raw::c_int,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetCreateFromMats(
+        nmat: i32,
+        data: *mut *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nrow: *mut i32,
+        ncol: i32,
+        is_row_major: ::std::os::raw::c_int,
+        parameters: *const ::std::os::raw::c_char,
+        reference: DatasetHandle,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetGetSubset(
+        handle: DatasetHandle,
+        used_row_indices: *const i32,
+        num_used_row_indices: i32,
+        parameters: *const ::std::os::raw::c_char,
+        out: *mut DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetSetFeatureNames(
+        handle: DatasetHandle,
+        feature_names: *mut *const ::std::os::raw::c_char,
+        num_feature_names: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetGetFeatureNames(
+        handle: DatasetHandle,
+        len: ::std::os::raw::c_int,
+        num_feature_names: *mut ::std::os::raw::c_int,
+        buffer_len: usize,
+        out_buffer_len: *mut usize,
+        feature_names: *mut *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetFree(
+        handle: DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetSaveBinary(
+        handle: DatasetHandle,
+        filename: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetDumpText(
+        handle: DatasetHandle,
+        filename: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetSetField(
+        handle: DatasetHandle,
+        field_name: *const ::std::os::raw::c_char,
+        field_data: *const ::std::os::raw::c_void,
+        num_element: ::std::os::raw::c_int,
+        type_: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetGetField(
+        handle: DatasetHandle,
+        field_name: *const ::std::os::raw::c_char,
+        out_len: *mut ::std::os::raw::c_int,
+        out_ptr: *mut *const ::std::os::raw::c_void,
+        out_type: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetUpdateParamChecking(
+        old_parameters: *const ::std::os::raw::c_char,
+        new_parameters: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetGetNumData(
+        handle: DatasetHandle,
+        out: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetGetNumFeature(
+        handle: DatasetHandle,
+        out: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_DatasetAddFeaturesFrom(
+        target: DatasetHandle,
+        source: DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterCreate(
+        train_data: DatasetHandle,
+        parameters: *const ::std::os::raw::c_char,
+        out: *mut BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterCreateFromModelfile(
+        filename: *const ::std::os::raw::c_char,
+        out_num_iterations: *mut ::std::os::raw::c_int,
+        out: *mut BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterLoadModelFromString(
+        model_str: *const ::std::os::raw::c_char,
+        out_num_iterations: *mut ::std::os::raw::c_int,
+        out: *mut BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterFree(
+        handle: BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterShuffleModels(
+        handle: BoosterHandle,
+        start_iter: ::std::os::raw::c_int,
+        end_iter: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterMerge(
+        handle: BoosterHandle,
+        other_handle: BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterAddValidData(
+        handle: BoosterHandle,
+        valid_data: DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterResetTrainingData(
+        handle: BoosterHandle,
+        train_data: DatasetHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterResetParameter(
+        handle: BoosterHandle,
+        parameters: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetNumClasses(
+        handle: BoosterHandle,
+        out_len: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterUpdateOneIter(
+        handle: BoosterHandle,
+        is_finished: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterRefit(
+        handle: BoosterHandle,
+        leaf_preds: *const i32,
+        nrow: i32,
+        ncol: i32,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterUpdateOneIterCustom(
+        handle: BoosterHandle,
+        grad: *const f32,
+        hess: *const f32,
+        is_finished: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterRollbackOneIter(
+        handle: BoosterHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetCurrentIteration(
+        handle: BoosterHandle,
+        out_iteration: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterNumModelPerIteration(
+        handle: BoosterHandle,
+        out_tree_per_iteration: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterNumberOfTotalModel(
+        handle: BoosterHandle,
+        out_models: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetEvalCounts(
+        handle: BoosterHandle,
+        out_len: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetEvalNames(
+        handle: BoosterHandle,
+        len: ::std::os::raw::c_int,
+        out_len: *mut ::std::os::raw::c_int,
+        buffer_len: usize,
+        out_buffer_len: *mut usize,
+        out_strs: *mut *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetFeatureNames(
+        handle: BoosterHandle,
+        len: ::std::os::raw::c_int,
+        out_len: *mut ::std::os::raw::c_int,
+        buffer_len: usize,
+        out_buffer_len: *mut usize,
+        out_strs: *mut *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetNumFeature(
+        handle: BoosterHandle,
+        out_len: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetEval(
+        handle: BoosterHandle,
+        data_idx: ::std::os::raw::c_int,
+        out_len: *mut ::std::os::raw::c_int,
+        out_results: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetNumPredict(
+        handle: BoosterHandle,
+        data_idx: ::std::os::raw::c_int,
+        out_len: *mut i64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetPredict(
+        handle: BoosterHandle,
+        data_idx: ::std::os::raw::c_int,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForFile(
+        handle: BoosterHandle,
+        data_filename: *const ::std::os::raw::c_char,
+        data_has_header: ::std::os::raw::c_int,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        result_filename: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterCalcNumPredict(
+        handle: BoosterHandle,
+        num_row: ::std::os::raw::c_int,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        out_len: *mut i64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_FastConfigFree(
+        fastConfig: FastConfigHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForCSR(
+        handle: BoosterHandle,
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nindptr: i64,
+        nelem: i64,
+        num_col: i64,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictSparseOutput(
+        handle: BoosterHandle,
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nindptr: i64,
+        nelem: i64,
+        num_col_or_row: i64,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        matrix_type: ::std::os::raw::c_int,
+        out_len: *mut i64,
+        out_indptr: *mut *mut ::std::os::raw::c_void,
+        out_indices: *mut *mut i32,
+        out_data: *mut *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterFreePredictSparse(
+        indptr: *mut ::std::os::raw::c_void,
+        indices: *mut i32,
+        data: *mut ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        data_type: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForCSRSingleRow(
+        handle: BoosterHandle,
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nindptr: i64,
+        nelem: i64,
+        num_col: i64,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForCSRSingleRowFastInit(
+        handle: BoosterHandle,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        data_type: ::std::os::raw::c_int,
+        num_col: i64,
+        parameter: *const ::std::os::raw::c_char,
+        out_fastConfig: *mut FastConfigHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForCSRSingleRowFast(
+        fastConfig_handle: FastConfigHandle,
+        indptr: *const ::std::os::raw::c_void,
+        indptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        nindptr: i64,
+        nelem: i64,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForCSC(
+        handle: BoosterHandle,
+        col_ptr: *const ::std::os::raw::c_void,
+        col_ptr_type: ::std::os::raw::c_int,
+        indices: *const i32,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        ncol_ptr: i64,
+        nelem: i64,
+        num_row: i64,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForMat(
+        handle: BoosterHandle,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nrow: i32,
+        ncol: i32,
+        is_row_major: ::std::os::raw::c_int,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForMatSingleRow(
+        handle: BoosterHandle,
+        data: *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        ncol: ::std::os::raw::c_int,
+        is_row_major: ::std::os::raw::c_int,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForMatSingleRowFastInit(
+        handle: BoosterHandle,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        data_type: ::std::os::raw::c_int,
+        ncol: i32,
+        parameter: *const ::std::os::raw::c_char,
+        out_fastConfig: *mut FastConfigHandle,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForMatSingleRowFast(
+        fastConfig_handle: FastConfigHandle,
+        data: *const ::std::os::raw::c_void,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterPredictForMats(
+        handle: BoosterHandle,
+        data: *mut *const ::std::os::raw::c_void,
+        data_type: ::std::os::raw::c_int,
+        nrow: i32,
+        ncol: i32,
+        predict_type: ::std::os::raw::c_int,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        parameter: *const ::std::os::raw::c_char,
+        out_len: *mut i64,
+        out_result: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterSaveModel(
+        handle: BoosterHandle,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        feature_importance_type: ::std::os::raw::c_int,
+        filename: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterSaveModelToString(
+        handle: BoosterHandle,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        feature_importance_type: ::std::os::raw::c_int,
+        buffer_len: i64,
+        out_len: *mut i64,
+        out_str: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterDumpModel(
+        handle: BoosterHandle,
+        start_iteration: ::std::os::raw::c_int,
+        num_iteration: ::std::os::raw::c_int,
+        feature_importance_type: ::std::os::raw::c_int,
+        buffer_len: i64,
+        out_len: *mut i64,
+        out_str: *mut ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetLeafValue(
+        handle: BoosterHandle,
+        tree_idx: ::std::os::raw::c_int,
+        leaf_idx: ::std::os::raw::c_int,
+        out_val: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterSetLeafValue(
+        handle: BoosterHandle,
+        tree_idx: ::std::os::raw::c_int,
+        leaf_idx: ::std::os::raw::c_int,
+        val: f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterFeatureImportance(
+        handle: BoosterHandle,
+        num_iteration: ::std::os::raw::c_int,
+        importance_type: ::std::os::raw::c_int,
+        out_results: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetUpperBoundValue(
+        handle: BoosterHandle,
+        out_results: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_BoosterGetLowerBoundValue(
+        handle: BoosterHandle,
+        out_results: *mut f64,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_NetworkInit(
+        machines: *const ::std::os::raw::c_char,
+        local_listen_port: ::std::os::raw::c_int,
+        listen_time_out: ::std::os::raw::c_int,
+        num_machines: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_NetworkFree() -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn LGBM_NetworkInitWithFunctions(
+        num_machines: ::std::os::raw::c_int,
+        rank: ::std::os::raw::c_int,
+        reduce_scatter_ext_fun: *mut ::std::os::raw::c_void,
+        allgather_ext_fun: *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
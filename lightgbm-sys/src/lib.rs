@@ -5,4 +5,11 @@
 #![allow(clippy::missing_safety_doc)]
 #![allow(clippy::upper_case_acronyms)]
 
+#[cfg(feature = "bindgen")]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// Bindings of the vendored LightGBM as generated by `build.rs`, with `size_t` as `usize`. CI
+// checks them against freshly generated ones; after updating LightGBM, copy
+// `$OUT_DIR/bindings.rs` of a build with the `bindgen` feature over them.
+#[cfg(not(feature = "bindgen"))]
+include!("bindings.rs");
@@ -0,0 +1,307 @@
+//! LightGBM-style `key=value` parameters, read from a config file and the command line.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::str::FromStr;
+
+use lightgbm::{Column, DatasetParameters, DatasetParametersBuilder};
+use serde_json::{Map, Number, Value};
+
+use CliResult;
+
+/// Aliases of the parameters handled by the tool, as in LightGBM's documentation.
+const ALIASES: &[(&str, &[&str])] = &[
+    (
+        "data",
+        &["train", "train_data", "train_data_file", "data_filename"],
+    ),
+    (
+        "valid",
+        &[
+            "test",
+            "valid_data",
+            "valid_data_file",
+            "test_data",
+            "test_data_file",
+            "valid_filenames",
+        ],
+    ),
+    ("input_model", &["model_input", "model_in"]),
+    ("output_model", &["model_output", "model_out"]),
+    (
+        "output_result",
+        &[
+            "predict_result",
+            "prediction_result",
+            "predict_name",
+            "prediction_name",
+            "pred_name",
+            "name_pred",
+        ],
+    ),
+    ("header", &["has_header"]),
+    ("label_column", &["label"]),
+    ("ignore_column", &["ignore_feature", "blacklist"]),
+    (
+        "categorical_feature",
+        &["cat_feature", "categorical_column", "cat_column"],
+    ),
+    (
+        "early_stopping_round",
+        &[
+            "early_stopping_rounds",
+            "early_stopping",
+            "n_iter_no_change",
+        ],
+    ),
+    ("metric_freq", &["output_freq"]),
+    (
+        "num_iterations",
+        &[
+            "num_iteration",
+            "n_iter",
+            "num_tree",
+            "num_trees",
+            "num_round",
+            "num_rounds",
+            "num_boost_round",
+            "n_estimators",
+        ],
+    ),
+];
+
+/// Parameters in the order of precedence of LightGBM's CLI: the command line over the config file.
+pub struct Config {
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Parse `key=value` arguments, reading the file of a `config=FILE` argument first.
+    pub fn from_args(args: &[String]) -> CliResult<Self> {
+        let mut values = BTreeMap::new();
+        for arg in args {
+            let (key, value) = parse_pair(arg).ok_or_else(|| {
+                format!("expected an argument of the form key=value, got '{}'", arg)
+            })?;
+            values.insert(key, value);
+        }
+        if let Some(path) = values.remove("config") {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("cannot read config file {}: {}", path, e))?;
+            for (line_idx, line) in content.lines().enumerate() {
+                let line = line.split('#').next().unwrap_or("").trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let (key, value) = parse_pair(line).ok_or_else(|| {
+                    format!(
+                        "{}:{}: expected key = value, got '{}'",
+                        path,
+                        line_idx + 1,
+                        line
+                    )
+                })?;
+                values.entry(key).or_insert(value);
+            }
+        }
+        Ok(Config { values })
+    }
+
+    /// Remove a parameter.
+    pub fn take(&mut self, key: &str) -> Option<String> {
+        self.values.remove(key)
+    }
+
+    /// Remove a parameter that must be given.
+    pub fn take_required(&mut self, key: &str) -> CliResult<String> {
+        self.take(key)
+            .ok_or_else(|| format!("missing parameter {}", key).into())
+    }
+
+    /// Remove and parse a parameter.
+    pub fn take_parsed<T: FromStr>(&mut self, key: &str) -> CliResult<Option<T>> {
+        match self.take(key) {
+            Some(value) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid value '{}' for {}", value, key).into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove a boolean parameter, accepting `true`/`false` and `1`/`0`.
+    pub fn take_bool(&mut self, key: &str) -> CliResult<Option<bool>> {
+        match self.take(key).as_deref() {
+            Some("true") | Some("1") => Ok(Some(true)),
+            Some("false") | Some("0") => Ok(Some(false)),
+            Some(value) => Err(format!(
+                "invalid value '{}' for {}, expected true or false",
+                value, key
+            )
+            .into()),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the parameters of loading a data file.
+    pub fn take_dataset_params(&mut self) -> CliResult<DatasetParameters> {
+        let mut builder = DatasetParametersBuilder::default();
+        if let Some(value) = self.take_parsed("max_bin")? {
+            builder.max_bin(value);
+        }
+        if let Some(value) = self.take_parsed("min_data_in_bin")? {
+            builder.min_data_in_bin(value);
+        }
+        if let Some(value) = self.take_parsed("bin_construct_sample_cnt")? {
+            builder.bin_construct_sample_cnt(value);
+        }
+        if let Some(value) = self.take_bool("header")? {
+            builder.header(value);
+        }
+        if let Some(value) = self.take_bool("use_missing")? {
+            builder.use_missing(value);
+        }
+        if let Some(value) = self.take_bool("zero_as_missing")? {
+            builder.zero_as_missing(value);
+        }
+        if let Some(value) = self.take("label_column") {
+            let mut columns = parse_columns(&value)?;
+            if columns.len() != 1 {
+                return Err(
+                    format!("label_column must be a single column, got '{}'", value).into(),
+                );
+            }
+            builder.label_column(columns.remove(0));
+        }
+        if let Some(value) = self.take("ignore_column") {
+            builder.ignore_column(parse_columns(&value)?);
+        }
+        if let Some(value) = self.take("categorical_feature") {
+            builder.categorical_feature(parse_columns(&value)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Fail on remaining parameters, which a command does not use and are most likely
+    /// misspelled.
+    pub fn finish(self) -> CliResult<()> {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+        let keys = self.values.keys().cloned().collect::<Vec<_>>();
+        Err(format!("unknown parameters: {}", keys.join(", ")).into())
+    }
+
+    /// The remaining parameters as a JSON object for a `Booster`, with numbers and booleans
+    /// converted.
+    pub fn into_json(self) -> Value {
+        let map = self
+            .values
+            .into_iter()
+            .map(|(key, value)| {
+                let value = if value == "true" || value == "false" {
+                    Value::Bool(value == "true")
+                } else if let Ok(number) = value.parse::<i64>() {
+                    Value::from(number)
+                } else if let Some(number) = value.parse::<f64>().ok().and_then(Number::from_f64) {
+                    Value::Number(number)
+                } else {
+                    Value::String(value)
+                };
+                (key, value)
+            })
+            .collect::<Map<_, _>>();
+        Value::Object(map)
+    }
+}
+
+/// Split `key=value`, trimming both sides and resolving aliases of the key.
+fn parse_pair(text: &str) -> Option<(String, String)> {
+    let mut parts = text.splitn(2, '=');
+    let key = parts.next()?.trim();
+    let value = parts.next()?.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let key = ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&key))
+        .map_or(key, |(name, _)| name);
+    Some((key.to_string(), value.to_string()))
+}
+
+/// Parse LightGBM's column syntax: indices such as `0,3` or names such as `name:age,color`.
+fn parse_columns(text: &str) -> CliResult<Vec<Column>> {
+    if let Some(names) = text.strip_prefix("name:") {
+        return Ok(names
+            .split(',')
+            .map(|name| Column::from(name.trim()))
+            .collect());
+    }
+    text.split(',')
+        .map(|index| {
+            index
+                .trim()
+                .parse::<usize>()
+                .map(Column::from)
+                .map_err(|_| format!("invalid column index '{}'", index).into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn command_line_overrides_config_file() {
+        let path = "./test/test_cli_config.input";
+        fs::write(
+            path,
+            "# training\ntask = train\nobjective = binary\n\
+             num_trees = 10\nlearning_rate=0.1 # comment\n",
+        )
+        .unwrap();
+        let mut config = Config::from_args(&args(&[
+            "config=./test/test_cli_config.input",
+            "num_iterations=3",
+            "header=true",
+        ]))
+        .unwrap();
+        let _ = fs::remove_file(path);
+
+        assert_eq!(config.take("task"), Some("train".to_string()));
+        assert_eq!(config.take_bool("header").unwrap(), Some(true));
+        assert_eq!(
+            config.into_json(),
+            serde_json::json!({"objective": "binary", "num_iterations": 3, "learning_rate": 0.1})
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        assert!(Config::from_args(&args(&["objective"])).is_err());
+        assert!(Config::from_args(&args(&["config=./test/missing.conf"])).is_err());
+        let mut config = Config::from_args(&args(&["header=yes", "max_bin=x"])).unwrap();
+        assert!(config.take_bool("header").is_err());
+        assert!(config.take_required("data").is_err());
+        assert!(config.take_dataset_params().is_err());
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(
+            parse_columns("0,3").unwrap(),
+            vec![Column::from(0), Column::from(3)]
+        );
+        assert_eq!(
+            parse_columns("name:age, color").unwrap(),
+            vec![Column::from("age"), Column::from("color")]
+        );
+        assert!(parse_columns("a,b").is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Graphviz DOT rendering of the trees of a model dump.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use tree;
+use CliResult;
+
+/// Render every tree of `model`, as returned by `Booster::dump_model`, as a cluster of one graph.
+///
+/// The edge taken by missing values is marked `missing`.
+pub fn model_to_dot(model: &Value) -> CliResult<String> {
+    let feature_names = model["feature_names"]
+        .as_array()
+        .map(|names| {
+            names
+                .iter()
+                .map(|name| name.as_str().unwrap_or("").to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let trees = model["tree_info"]
+        .as_array()
+        .ok_or("the model dump has no tree_info")?;
+
+    let mut dot = String::from("digraph model {\n    node [shape=ellipse];\n");
+    for (tree_idx, tree) in trees.iter().enumerate() {
+        writeln!(dot, "    subgraph cluster_tree{} {{", tree_idx)?;
+        writeln!(dot, "        label=\"tree {}\";", tree_idx)?;
+        write_node(&mut dot, tree_idx, &tree["tree_structure"], &feature_names)?;
+        dot.push_str("    }\n");
+    }
+    dot.push_str("}\n");
+    Ok(dot)
+}
+
+/// Write `node` and its subtree, returning the DOT identifier of `node`.
+fn write_node(
+    dot: &mut String,
+    tree_idx: usize,
+    node: &Value,
+    feature_names: &[String],
+) -> CliResult<String> {
+    if let Some(split_index) = node["split_index"].as_u64() {
+        let id = format!("t{}s{}", tree_idx, split_index);
+        let feature = node["split_feature"]
+            .as_u64()
+            .ok_or("a split has no split_feature")? as usize;
+        let feature_name = feature_names
+            .get(feature)
+            .cloned()
+            .unwrap_or_else(|| format!("Column_{}", feature));
+        let threshold = match node["threshold"] {
+            Value::String(ref threshold) => threshold.clone(),
+            ref threshold => threshold.to_string(),
+        };
+        writeln!(
+            dot,
+            "        {} [label=\"{} {} {}\"];",
+            id,
+            escape(&feature_name),
+            escape(node["decision_type"].as_str().unwrap_or("<=")),
+            escape(&threshold)
+        )?;
+        let missing_left = tree::missing_goes_left(node);
+        let left = write_node(dot, tree_idx, &node["left_child"], feature_names)?;
+        let right = write_node(dot, tree_idx, &node["right_child"], feature_names)?;
+        writeln!(
+            dot,
+            "        {} -> {} [label=\"{}\"];",
+            id,
+            left,
+            if missing_left { "yes, missing" } else { "yes" }
+        )?;
+        writeln!(
+            dot,
+            "        {} -> {} [label=\"{}\"];",
+            id,
+            right,
+            if missing_left { "no" } else { "no, missing" }
+        )?;
+        Ok(id)
+    } else {
+        let leaf_value = node["leaf_value"]
+            .as_f64()
+            .ok_or("a tree node is neither a split nor a leaf")?;
+        let leaf_index = node["leaf_index"].as_u64().unwrap_or(0);
+        let id = format!("t{}l{}", tree_idx, leaf_index);
+        writeln!(
+            dot,
+            "        {} [label=\"leaf {}: {}\", shape=box];",
+            id, leaf_index, leaf_value
+        )?;
+        Ok(id)
+    }
+}
+
+/// Escape a string for a quoted DOT label.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render() {
+        let model = json!({
+            "feature_names": ["age", "size \"cm\""],
+            "tree_info": [
+                {"tree_structure": {
+                    "split_index": 0, "split_feature": 1, "threshold": 1.5,
+                    "decision_type": "<=", "default_left": false, "missing_type": "NaN",
+                    "left_child": {"leaf_index": 0, "leaf_value": -0.5},
+                    "right_child": {"leaf_index": 1, "leaf_value": 0.5}
+                }},
+                {"tree_structure": {
+                    "split_index": 0, "split_feature": 0, "threshold": 2.5,
+                    "decision_type": "<=", "default_left": false, "missing_type": "None",
+                    "left_child": {"leaf_index": 0, "leaf_value": -0.1},
+                    "right_child": {"leaf_index": 1, "leaf_value": 0.1}
+                }},
+                {"tree_structure": {"leaf_value": 0.1}}
+            ]
+        });
+        let dot = model_to_dot(&model).unwrap();
+        assert!(dot.starts_with("digraph model {"));
+        assert!(dot.contains("t0s0 [label=\"size \\\"cm\\\" <= 1.5\"];"));
+        assert!(dot.contains("t0s0 -> t0l0 [label=\"yes\"];"));
+        assert!(dot.contains("t0s0 -> t0l1 [label=\"no, missing\"];"));
+        assert!(dot.contains("t1s0 -> t1l0 [label=\"yes, missing\"];"));
+        assert!(dot.contains("t1s0 -> t1l1 [label=\"no\"];"));
+        assert!(dot.contains("t2l0 [label=\"leaf 0: 0.1\", shape=box];"));
+        assert!(model_to_dot(&json!({})).is_err());
+    }
+}
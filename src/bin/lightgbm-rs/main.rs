@@ -0,0 +1,388 @@
+//! Command-line tool to train, predict with, convert and inspect LightGBM models.
+//!
+//! Parameters are given as `key=value` like for LightGBM's own CLI, optionally read from a
+//! `config=FILE` file with one `key = value` per line; command-line values take precedence.
+
+extern crate lightgbm;
+extern crate serde_json;
+
+mod config;
+mod dot;
+mod onnx;
+mod tree;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::process;
+
+use lightgbm::{is_higher_better, Booster, Dataset, PredictParametersBuilder, PredictType};
+
+use config::Config;
+
+/// Result of a command, with an error message for the user.
+pub type CliResult<T> = Result<T, Box<dyn Error>>;
+
+const USAGE: &str = "\
+Usage: lightgbm-rs <command> [config=FILE] [key=value ...]
+
+Commands:
+  train    Train a model.
+           data=FILE [valid=FILE,...] [output_model=FILE] [num_iterations=N]
+           [early_stopping_round=N] [metric_freq=N] and training parameters
+  predict  Predict the rows of a data file.
+           input_model=FILE data=FILE [output_result=FILE] [header=true]
+           [predict_raw_score=true | predict_leaf_index=true | predict_contrib=true]
+           [start_iteration_predict=N] [num_iteration_predict=N]
+  convert  Convert a model file.
+           input_model=FILE format=json|dot|onnx [output=FILE]
+  inspect  Print the objective, trees, features and feature importance of a model.
+           input_model=FILE
+";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> CliResult<()> {
+    let (command, args) = match args.split_first() {
+        Some(split) => split,
+        None => {
+            print!("{}", USAGE);
+            return Ok(());
+        }
+    };
+    let config = Config::from_args(args)?;
+    match command.as_str() {
+        "train" => train(config),
+        "predict" => predict(config),
+        "convert" => convert(config),
+        "inspect" => inspect(config),
+        "help" | "-h" | "--help" => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(format!("unknown command '{}'\n\n{}", command, USAGE).into()),
+    }
+}
+
+fn train(mut config: Config) -> CliResult<()> {
+    // Accepted for config files written for LightGBM's CLI.
+    config.take("task");
+    let data = config.take_required("data")?;
+    let valid = config.take("valid");
+    let output_model = config
+        .take("output_model")
+        .unwrap_or_else(|| "LightGBM_model.txt".to_string());
+    let dataset_params = config.take_dataset_params()?;
+    let num_iterations = config
+        .take_parsed::<usize>("num_iterations")?
+        .unwrap_or(100);
+    let early_stopping_round = config
+        .take_parsed::<usize>("early_stopping_round")?
+        .filter(|&rounds| rounds > 0);
+    let metric_freq = config.take_parsed::<usize>("metric_freq")?.unwrap_or(1);
+    if early_stopping_round.is_some() && valid.is_none() {
+        return Err("early_stopping_round needs validation data".into());
+    }
+
+    let train_data = Dataset::from_file_with_params(&data, &dataset_params, None)?;
+    let valid_files = valid.as_ref().map_or(Vec::new(), |valid| {
+        valid.split(',').map(str::trim).collect()
+    });
+    let valid_data = valid_files
+        .iter()
+        .map(|file| Dataset::from_file_with_params(file, &dataset_params, Some(&train_data)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut booster = Booster::create(train_data, &config.into_json())?;
+    for dataset in valid_data {
+        booster.add_valid_data(dataset)?;
+    }
+    let eval_names = booster.eval_names()?;
+    if early_stopping_round.is_some() && eval_names.is_empty() {
+        return Err("early_stopping_round needs at least one metric".into());
+    }
+    let higher_better = eval_names
+        .first()
+        .is_some_and(|name| is_higher_better(name));
+    let eval_files = Some(data.as_str())
+        .into_iter()
+        .chain(valid_files)
+        .collect::<Vec<_>>();
+
+    // (iteration, score) of the best first metric on the first validation data
+    let mut best: Option<(usize, f64)> = None;
+    for iteration in 1..=num_iterations {
+        if booster.update_one_iter()? {
+            println!("[{}] training finished, no further splits", iteration);
+            break;
+        }
+        if metric_freq > 0 && iteration % metric_freq == 0 {
+            for (data_idx, file) in eval_files.iter().enumerate() {
+                for (name, value) in eval_names.iter().zip(booster.eval(data_idx)?) {
+                    println!("[{}] {} {}: {}", iteration, file, name, value);
+                }
+            }
+        }
+        if let Some(rounds) = early_stopping_round {
+            let score = booster.eval(1)?[0];
+            match best {
+                Some((_, best_score))
+                    if (higher_better && score <= best_score)
+                        || (!higher_better && score >= best_score) => {}
+                _ => best = Some((iteration, score)),
+            }
+            let (best_iteration, best_score) = best.unwrap();
+            if iteration - best_iteration >= rounds {
+                for _ in best_iteration..iteration {
+                    booster.rollback_one_iter()?;
+                }
+                println!(
+                    "early stopping at iteration {}, best iteration {} with {} {}",
+                    iteration, best_iteration, eval_names[0], best_score
+                );
+                break;
+            }
+        }
+    }
+    booster.save_file(&output_model)?;
+    println!("saved model to {}", output_model);
+    Ok(())
+}
+
+fn predict(mut config: Config) -> CliResult<()> {
+    config.take("task");
+    let input_model = config.take_required("input_model")?;
+    let data = config.take_required("data")?;
+    let output_result = config
+        .take("output_result")
+        .unwrap_or_else(|| "LightGBM_predict_result.txt".to_string());
+    let header = config.take_bool("header")?.unwrap_or(false);
+
+    let predict_types = [
+        ("predict_raw_score", PredictType::RawScore),
+        ("predict_leaf_index", PredictType::LeafIndex),
+        ("predict_contrib", PredictType::Contrib),
+    ];
+    let mut builder = PredictParametersBuilder::default();
+    let mut selected = Vec::new();
+    for &(key, predict_type) in &predict_types {
+        if config.take_bool(key)?.unwrap_or(false) {
+            builder.predict_type(predict_type);
+            selected.push(key);
+        }
+    }
+    if selected.len() > 1 {
+        return Err(format!("only one of {} can be set", selected.join(", ")).into());
+    }
+    if let Some(value) = config.take_parsed("start_iteration_predict")? {
+        builder.start_iteration(value);
+    }
+    if let Some(value) = config.take_parsed("num_iteration_predict")? {
+        builder.num_iteration(value);
+    }
+    let params = builder.build()?;
+    config.finish()?;
+
+    let booster = Booster::from_file(&input_model)?;
+    booster.predict_file(&data, header, &output_result, &params)?;
+    println!("saved predictions to {}", output_result);
+    Ok(())
+}
+
+fn convert(mut config: Config) -> CliResult<()> {
+    let input_model = config.take_required("input_model")?;
+    let format = config.take_required("format")?;
+    let output = config.take("output");
+    config.finish()?;
+
+    let model = Booster::from_file(&input_model)?.dump_model()?;
+    let converted = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&model)?.into_bytes(),
+        "dot" => dot::model_to_dot(&model)?.into_bytes(),
+        "onnx" => onnx::model_to_onnx(&model)?,
+        _ => {
+            let message = format!("unknown format '{}', expected json, dot or onnx", format);
+            return Err(message.into());
+        }
+    };
+    match output {
+        Some(output) => fs::write(&output, converted)
+            .map_err(|e| format!("cannot write {}: {}", output, e).into()),
+        None if format == "onnx" => Err("output is required for the onnx format".into()),
+        None => {
+            println!("{}", String::from_utf8(converted)?);
+            Ok(())
+        }
+    }
+}
+
+fn inspect(mut config: Config) -> CliResult<()> {
+    let input_model = config.take_required("input_model")?;
+    config.finish()?;
+
+    let booster = Booster::from_file(&input_model)?;
+    let model = booster.dump_model()?;
+    let num_trees = model["tree_info"].as_array().map_or(0, Vec::len);
+    let trees_per_iteration = model["num_tree_per_iteration"].as_u64().unwrap_or(1) as usize;
+    println!("objective: {}", model["objective"].as_str().unwrap_or(""));
+    println!("num_class: {}", booster.num_class()?);
+    println!(
+        "trees: {} ({} iterations of {} trees)",
+        num_trees,
+        num_trees / trees_per_iteration.max(1),
+        trees_per_iteration
+    );
+    println!("features: {}", booster.num_feature()?);
+
+    let feature_name = booster.feature_name()?;
+    let splits = booster.feature_importance()?;
+    let mut gains = vec![0.0; feature_name.len()];
+    if let Some(trees) = model["tree_info"].as_array() {
+        for tree in trees {
+            add_split_gains(&tree["tree_structure"], &mut gains);
+        }
+    }
+    let mut order = (0..feature_name.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| gains[b].total_cmp(&gains[a]));
+    let width = feature_name
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max(7);
+    println!("feature importance:");
+    println!(
+        "  {:<width$} {:>8} {:>14}",
+        "feature",
+        "splits",
+        "gain",
+        width = width
+    );
+    for idx in order {
+        println!(
+            "  {:<width$} {:>8} {:>14.4}",
+            feature_name[idx],
+            splits[idx],
+            gains[idx],
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// Add the gain of every split of the tree under `node` to its feature.
+fn add_split_gains(node: &serde_json::Value, gains: &mut [f64]) {
+    if let Some(feature) = node["split_feature"].as_u64() {
+        if let Some(gain) = gains.get_mut(feature as usize) {
+            *gain += node["split_gain"].as_f64().unwrap_or(0.0);
+        }
+        add_split_gains(&node["left_child"], gains);
+        add_split_gains(&node["right_child"], gains);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn train_predict_convert_inspect() {
+        let data = "data=lightgbm-sys/lightgbm/examples/binary_classification/binary.train";
+        let test = "lightgbm-sys/lightgbm/examples/binary_classification/binary.test";
+        let model = "./test/test_cli_model.output";
+        let result = "./test/test_cli_predict.output";
+        let converted = "./test/test_cli_convert.output";
+
+        run(&args(&[
+            "train",
+            data,
+            &format!("valid={}", test),
+            &format!("output_model={}", model),
+            "objective=binary",
+            "num_trees=3",
+            "metric=auc",
+        ]))
+        .unwrap();
+        assert_eq!(
+            Booster::from_file(model).unwrap().dump_model().unwrap()["tree_info"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+
+        run(&args(&[
+            "predict",
+            &format!("input_model={}", model),
+            &format!("data={}", test),
+            &format!("output_result={}", result),
+        ]))
+        .unwrap();
+        assert_eq!(fs::read_to_string(result).unwrap().lines().count(), 500);
+
+        for format in &["json", "dot", "onnx"] {
+            run(&args(&[
+                "convert",
+                &format!("input_model={}", model),
+                &format!("format={}", format),
+                &format!("output={}", converted),
+            ]))
+            .unwrap();
+            assert!(!fs::read(converted).unwrap().is_empty());
+        }
+
+        run(&args(&["inspect", &format!("input_model={}", model)])).unwrap();
+        assert!(run(&args(&[
+            "inspect",
+            &format!("input_model={}", model),
+            "verbose=1"
+        ]))
+        .is_err());
+        assert!(run(&args(&["unknown"])).is_err());
+
+        for file in &[model, result, converted] {
+            let _ = fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn train_early_stopping() {
+        let data = "data=lightgbm-sys/lightgbm/examples/binary_classification/binary.train";
+        let test = "lightgbm-sys/lightgbm/examples/binary_classification/binary.test";
+        let model = "./test/test_cli_early_stopping.output";
+
+        run(&args(&[
+            "train",
+            data,
+            &format!("valid={}", test),
+            &format!("output_model={}", model),
+            "objective=binary",
+            "metric=binary_logloss",
+            "learning_rate=1.0",
+            "num_leaves=255",
+            "min_data_in_leaf=1",
+            "num_trees=200",
+            "early_stopping_round=2",
+            "metric_freq=10",
+        ]))
+        .unwrap();
+        let num_trees = Booster::from_file(model).unwrap().dump_model().unwrap()["tree_info"]
+            .as_array()
+            .unwrap()
+            .len();
+        let _ = fs::remove_file(model);
+        assert!(num_trees < 200);
+
+        assert!(run(&args(&["train", data, "early_stopping_round=2"])).is_err());
+    }
+}
@@ -0,0 +1,449 @@
+//! ONNX export of a model dump as a single `ai.onnx.ml` `TreeEnsembleRegressor` node.
+//!
+//! The graph maps a float tensor `input` of shape `[N, num_feature]` to a float tensor
+//! `variable` of shape `[N, num_class]`, transformed like LightGBM's normal prediction. Only
+//! models whose objective transformation and splits ONNX can express are exported: regression
+//! objectives without transformation, `binary` with `sigmoid:1` and `multiclass`, with
+//! numerical splits and constant leaves, i.e. no linear trees. Inputs are compared as `f32`, so
+//! thresholds are rounded down to the nearest `f32`, which keeps the decisions of LightGBM for
+//! `f32` inputs. Models averaging their trees, e.g. random forests, are exported with leaf
+//! values divided by the number of iterations.
+
+use serde_json::Value;
+
+use tree;
+use CliResult;
+
+const IR_VERSION: u64 = 7;
+const OPSET_VERSION: u64 = 13;
+const ML_OPSET_VERSION: u64 = 1;
+const TENSOR_FLOAT: u64 = 1;
+
+/// Objectives whose normal prediction is the raw score.
+const IDENTITY_OBJECTIVES: &[&str] = &[
+    "regression",
+    "regression_l1",
+    "huber",
+    "fair",
+    "quantile",
+    "mape",
+];
+
+/// Encode `model`, as returned by `Booster::dump_model`, as an ONNX `ModelProto`.
+pub fn model_to_onnx(model: &Value) -> CliResult<Vec<u8>> {
+    let objective = model["objective"].as_str().unwrap_or("");
+    let post_transform = post_transform(objective)?;
+    let num_targets = model["num_tree_per_iteration"].as_u64().unwrap_or(1);
+    let num_feature = model["max_feature_idx"]
+        .as_u64()
+        .ok_or("the model dump has no max_feature_idx")?
+        + 1;
+    let trees = model["tree_info"]
+        .as_array()
+        .ok_or("the model dump has no tree_info")?;
+
+    let leaf_scale = if model["average_output"].as_bool().unwrap_or(false) {
+        let num_iterations = trees.len() as u64 / num_targets.max(1);
+        1.0 / num_iterations.max(1) as f64
+    } else {
+        1.0
+    };
+
+    let mut ensemble = Ensemble {
+        leaf_scale,
+        ..Ensemble::default()
+    };
+    for (tree_idx, tree) in trees.iter().enumerate() {
+        let mut next_id = 0;
+        ensemble.add_node(
+            &tree["tree_structure"],
+            tree_idx as u64,
+            tree_idx as u64 % num_targets,
+            &mut next_id,
+        )?;
+    }
+
+    let mut node = Message::default();
+    node.string(1, "input");
+    node.string(2, "variable");
+    node.string(3, "TreeEnsembleRegressor");
+    node.string(4, "TreeEnsembleRegressor");
+    node.string(7, "ai.onnx.ml");
+    node.message(5, &attribute_int("n_targets", num_targets));
+    node.message(5, &attribute_string("aggregate_function", "SUM"));
+    node.message(5, &attribute_string("post_transform", post_transform));
+    node.message(5, &attribute_ints("nodes_treeids", &ensemble.tree_ids));
+    node.message(5, &attribute_ints("nodes_nodeids", &ensemble.node_ids));
+    node.message(
+        5,
+        &attribute_ints("nodes_featureids", &ensemble.feature_ids),
+    );
+    node.message(5, &attribute_floats("nodes_values", &ensemble.values));
+    node.message(5, &attribute_strings("nodes_modes", &ensemble.modes));
+    node.message(5, &attribute_ints("nodes_truenodeids", &ensemble.true_ids));
+    node.message(
+        5,
+        &attribute_ints("nodes_falsenodeids", &ensemble.false_ids),
+    );
+    node.message(
+        5,
+        &attribute_ints(
+            "nodes_missing_value_tracks_true",
+            &ensemble.missing_tracks_true,
+        ),
+    );
+    node.message(
+        5,
+        &attribute_ints("target_treeids", &ensemble.target_tree_ids),
+    );
+    node.message(
+        5,
+        &attribute_ints("target_nodeids", &ensemble.target_node_ids),
+    );
+    node.message(5, &attribute_ints("target_ids", &ensemble.target_ids));
+    node.message(
+        5,
+        &attribute_floats("target_weights", &ensemble.target_weights),
+    );
+
+    let mut graph = Message::default();
+    graph.message(1, &node);
+    graph.string(2, "lightgbm");
+    graph.message(11, &tensor_info("input", num_feature));
+    graph.message(12, &tensor_info("variable", num_targets));
+
+    let mut model_proto = Message::default();
+    model_proto.varint(1, IR_VERSION);
+    model_proto.string(2, "lightgbm-rs");
+    model_proto.string(3, env!("CARGO_PKG_VERSION"));
+    model_proto.message(7, &graph);
+    model_proto.message(8, &opset("", OPSET_VERSION));
+    model_proto.message(8, &opset("ai.onnx.ml", ML_OPSET_VERSION));
+    Ok(model_proto.buf)
+}
+
+/// The ONNX post transform equal to the transformation of `objective`, as written in the dump.
+fn post_transform(objective: &str) -> CliResult<&'static str> {
+    let mut tokens = objective.split_whitespace();
+    let name = tokens.next().unwrap_or("");
+    let options = tokens.collect::<Vec<_>>();
+    if IDENTITY_OBJECTIVES.contains(&name) && !options.contains(&"sqrt") {
+        Ok("NONE")
+    } else if name == "binary" && options.contains(&"sigmoid:1") {
+        Ok("LOGISTIC")
+    } else if name == "multiclass" {
+        Ok("SOFTMAX")
+    } else {
+        Err(format!("ONNX export does not support the objective '{}'", objective).into())
+    }
+}
+
+/// Parallel node and target attributes of a tree ensemble.
+#[derive(Default)]
+struct Ensemble {
+    tree_ids: Vec<u64>,
+    node_ids: Vec<u64>,
+    feature_ids: Vec<u64>,
+    values: Vec<f32>,
+    modes: Vec<&'static str>,
+    true_ids: Vec<u64>,
+    false_ids: Vec<u64>,
+    missing_tracks_true: Vec<u64>,
+    target_tree_ids: Vec<u64>,
+    target_node_ids: Vec<u64>,
+    target_ids: Vec<u64>,
+    target_weights: Vec<f32>,
+    /// Factor of the leaf values, below 1 for models averaging their trees.
+    leaf_scale: f64,
+}
+
+impl Ensemble {
+    /// Add `node` and its subtree in pre-order, returning the id of `node`.
+    fn add_node(
+        &mut self,
+        node: &Value,
+        tree_id: u64,
+        target_id: u64,
+        next_id: &mut u64,
+    ) -> CliResult<u64> {
+        let id = *next_id;
+        *next_id += 1;
+        let position = self.node_ids.len();
+        self.tree_ids.push(tree_id);
+        self.node_ids.push(id);
+
+        if node["split_index"].is_u64() {
+            if node["decision_type"] != "<=" {
+                return Err("ONNX export does not support categorical splits".into());
+            }
+            let feature = node["split_feature"]
+                .as_u64()
+                .ok_or("a split has no split_feature")?;
+            let threshold = node["threshold"]
+                .as_f64()
+                .ok_or("a split has no numerical threshold")?;
+            let default_left = node["default_left"].as_bool().unwrap_or(true);
+            match node["missing_type"].as_str().unwrap_or("None") {
+                "None" | "NaN" => {}
+                // Zero goes to the default side, which is only expressible when the comparison
+                // agrees with it.
+                "Zero" if default_left == (0.0 <= threshold) => {}
+                missing_type => {
+                    return Err(format!(
+                        "ONNX export does not support missing type {} with this threshold",
+                        missing_type
+                    )
+                    .into())
+                }
+            }
+            let nan_left = tree::missing_goes_left(node);
+            self.feature_ids.push(feature);
+            self.values.push(floor_f32(threshold));
+            self.modes.push("BRANCH_LEQ");
+            self.true_ids.push(0);
+            self.false_ids.push(0);
+            self.missing_tracks_true.push(nan_left as u64);
+            self.true_ids[position] =
+                self.add_node(&node["left_child"], tree_id, target_id, next_id)?;
+            self.false_ids[position] =
+                self.add_node(&node["right_child"], tree_id, target_id, next_id)?;
+        } else {
+            let leaf_value = node["leaf_value"]
+                .as_f64()
+                .ok_or("a tree node is neither a split nor a leaf")?;
+            if !node["leaf_coeff"].is_null() {
+                return Err("ONNX export does not support linear trees".into());
+            }
+            self.feature_ids.push(0);
+            self.values.push(0.0);
+            self.modes.push("LEAF");
+            self.true_ids.push(0);
+            self.false_ids.push(0);
+            self.missing_tracks_true.push(0);
+            self.target_tree_ids.push(tree_id);
+            self.target_node_ids.push(id);
+            self.target_ids.push(target_id);
+            self.target_weights
+                .push((leaf_value * self.leaf_scale) as f32);
+        }
+        Ok(id)
+    }
+}
+
+/// The largest `f32` not greater than `value`, so that `x <= value` equals `x <= result` for
+/// every `f32` `x`.
+fn floor_f32(value: f64) -> f32 {
+    let rounded = value as f32;
+    if f64::from(rounded) <= value {
+        rounded
+    } else if rounded == 0.0 {
+        -f32::from_bits(1)
+    } else if rounded > 0.0 {
+        f32::from_bits(rounded.to_bits() - 1)
+    } else {
+        f32::from_bits(rounded.to_bits() + 1)
+    }
+}
+
+/// A protobuf message under construction, with fields appended in order.
+#[derive(Default)]
+struct Message {
+    buf: Vec<u8>,
+}
+
+impl Message {
+    fn key(&mut self, field: u32, wire_type: u32) {
+        self.raw_varint(u64::from(field << 3 | wire_type));
+    }
+
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn varint(&mut self, field: u32, value: u64) {
+        self.key(field, 0);
+        self.raw_varint(value);
+    }
+
+    fn float(&mut self, field: u32, value: f32) {
+        self.key(field, 5);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.key(field, 2);
+        self.raw_varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    fn message(&mut self, field: u32, value: &Message) {
+        self.bytes(field, &value.buf);
+    }
+}
+
+// `AttributeProto.AttributeType` values.
+const ATTRIBUTE_INT: u64 = 2;
+const ATTRIBUTE_STRING: u64 = 3;
+const ATTRIBUTE_FLOATS: u64 = 6;
+const ATTRIBUTE_INTS: u64 = 7;
+const ATTRIBUTE_STRINGS: u64 = 8;
+
+fn attribute(name: &str, attribute_type: u64) -> Message {
+    let mut attribute = Message::default();
+    attribute.string(1, name);
+    attribute.varint(20, attribute_type);
+    attribute
+}
+
+fn attribute_int(name: &str, value: u64) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_INT);
+    attribute.varint(3, value);
+    attribute
+}
+
+fn attribute_string(name: &str, value: &str) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_STRING);
+    attribute.string(4, value);
+    attribute
+}
+
+fn attribute_ints(name: &str, values: &[u64]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_INTS);
+    for &value in values {
+        attribute.varint(8, value);
+    }
+    attribute
+}
+
+fn attribute_floats(name: &str, values: &[f32]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_FLOATS);
+    for &value in values {
+        attribute.float(7, value);
+    }
+    attribute
+}
+
+fn attribute_strings(name: &str, values: &[&str]) -> Message {
+    let mut attribute = attribute(name, ATTRIBUTE_STRINGS);
+    for value in values {
+        attribute.string(9, value);
+    }
+    attribute
+}
+
+/// `ValueInfoProto` of a float tensor of shape `[N, num_cols]`.
+fn tensor_info(name: &str, num_cols: u64) -> Message {
+    let mut rows = Message::default();
+    rows.string(2, "N");
+    let mut cols = Message::default();
+    cols.varint(1, num_cols);
+    let mut shape = Message::default();
+    shape.message(1, &rows);
+    shape.message(1, &cols);
+    let mut tensor = Message::default();
+    tensor.varint(1, TENSOR_FLOAT);
+    tensor.message(2, &shape);
+    let mut tensor_type = Message::default();
+    tensor_type.message(1, &tensor);
+    let mut info = Message::default();
+    info.string(1, name);
+    info.message(2, &tensor_type);
+    info
+}
+
+fn opset(domain: &str, version: u64) -> Message {
+    let mut opset = Message::default();
+    opset.string(1, domain);
+    opset.varint(2, version);
+    opset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn varint() {
+        let mut message = Message::default();
+        message.varint(1, 300);
+        assert_eq!(message.buf, vec![0x08, 0xac, 0x02]);
+        let mut message = Message::default();
+        message.string(2, "ab");
+        assert_eq!(message.buf, vec![0x12, 0x02, b'a', b'b']);
+    }
+
+    #[test]
+    fn threshold_rounding() {
+        assert_eq!(floor_f32(1.5), 1.5);
+        let below = floor_f32(0.1);
+        assert!(f64::from(below) <= 0.1);
+        assert!(f64::from(f32::from_bits(below.to_bits() + 1)) > 0.1);
+        assert!(f64::from(floor_f32(-0.1)) <= -0.1);
+        assert!(floor_f32(1e-300) >= 0.0);
+        assert!(floor_f32(-1e-300) < 0.0);
+    }
+
+    #[test]
+    fn objectives() {
+        assert_eq!(post_transform("regression").unwrap(), "NONE");
+        assert_eq!(post_transform("binary sigmoid:1").unwrap(), "LOGISTIC");
+        assert_eq!(post_transform("multiclass num_class:3").unwrap(), "SOFTMAX");
+        assert!(post_transform("binary sigmoid:2").is_err());
+        assert!(post_transform("regression sqrt").is_err());
+        assert!(post_transform("poisson").is_err());
+    }
+
+    #[test]
+    fn encode() {
+        let model = json!({
+            "objective": "binary sigmoid:1",
+            "max_feature_idx": 1,
+            "num_tree_per_iteration": 1,
+            "tree_info": [
+                {"tree_structure": {
+                    "split_index": 0, "split_feature": 1, "threshold": 1.5,
+                    "decision_type": "<=", "default_left": true, "missing_type": "NaN",
+                    "left_child": {"leaf_index": 0, "leaf_value": -0.5},
+                    "right_child": {"leaf_index": 1, "leaf_value": 0.5}
+                }}
+            ]
+        });
+        let encoded = model_to_onnx(&model).unwrap();
+        assert_eq!(&encoded[..2], &[0x08, 0x07]);
+        let contains = |needle: &[u8]| encoded.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"TreeEnsembleRegressor"));
+        assert!(contains(b"LOGISTIC"));
+        assert!(contains(&1.5_f32.to_le_bytes()));
+
+        let mut categorical = model.clone();
+        categorical["tree_info"][0]["tree_structure"]["decision_type"] = json!("==");
+        assert!(model_to_onnx(&categorical).is_err());
+        let mut zero = model.clone();
+        zero["tree_info"][0]["tree_structure"]["missing_type"] = json!("Zero");
+        zero["tree_info"][0]["tree_structure"]["default_left"] = json!(false);
+        assert!(model_to_onnx(&zero).is_err());
+        let mut linear = model.clone();
+        linear["tree_info"][0]["tree_structure"]["left_child"]["leaf_coeff"] = json!([0.5]);
+        assert!(model_to_onnx(&linear).is_err());
+
+        let mut average = model;
+        average["average_output"] = json!(true);
+        average["tree_info"] = json!([
+            average["tree_info"][0].clone(),
+            average["tree_info"][0].clone()
+        ]);
+        let encoded = model_to_onnx(&average).unwrap();
+        let contains = |needle: &[u8]| encoded.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(&(-0.25_f32).to_le_bytes()));
+        assert!(!contains(&(-0.5_f32).to_le_bytes()));
+    }
+}
@@ -0,0 +1,35 @@
+//! Decisions of the tree nodes of a model dump.
+
+use serde_json::Value;
+
+/// Whether missing values go down the left child of the split `node`.
+///
+/// With missing type `NaN` or `Zero`, missing values follow `default_left`; without a missing
+/// type, LightGBM compares them as zero.
+pub fn missing_goes_left(node: &Value) -> bool {
+    match node["missing_type"].as_str() {
+        Some("NaN") | Some("Zero") => node["default_left"].as_bool().unwrap_or(true),
+        _ => node["threshold"].as_f64().is_some_and(|t| 0.0 <= t),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn missing_values() {
+        let node = |missing_type: &str, default_left: bool, threshold: f64| {
+            json!({
+                "missing_type": missing_type,
+                "default_left": default_left,
+                "threshold": threshold
+            })
+        };
+        assert!(missing_goes_left(&node("NaN", true, -1.0)));
+        assert!(!missing_goes_left(&node("Zero", false, 1.0)));
+        assert!(missing_goes_left(&node("None", false, 0.0)));
+        assert!(!missing_goes_left(&node("None", true, -0.5)));
+    }
+}
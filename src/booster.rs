@@ -1,26 +1,90 @@
 use libc::{c_char, c_double, c_longlong, c_void};
 use std;
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::panic;
+use std::sync::Arc;
+use std::thread;
 
 use serde_json::Value;
 
 use lightgbm_sys;
 
-use crate::{Dataset, Error, Result};
+#[cfg(feature = "ndarray")]
+use ndarray::{Array2, ArrayView2};
+
+#[cfg(feature = "arrow")]
+use arrow::record_batch::RecordBatch;
+
+#[cfg(feature = "dataframe")]
+use polars::prelude::DataFrame;
+
+#[cfg(feature = "dataframe")]
+use crate::dataframe;
+use crate::matrix::flatten_rows;
+#[cfg(feature = "ndarray")]
+use crate::matrix::with_array_view;
+#[cfg(feature = "arrow")]
+use crate::record_batch;
+use crate::utils::read_string_array;
+use crate::{
+    CategoricalEncoder, DType, Dataset, Error, MatrixLayout, MatrixView, PredictParameters,
+    PredictParametersBuilder, PredictType, Prediction, Result,
+};
 
 /// Core model in LightGBM, containing functions for training, evaluating and predicting.
+///
+/// ## Thread safety
+///
+/// A `Booster` is `Send` and `Sync`, so one model can be shared between threads, for example
+/// in an `Arc`. LightGBM guards each booster with a readers-writer lock: predictions and other
+/// methods taking `&self` may run concurrently, while training and adding validation data take
+/// `&mut self` and therefore have exclusive access.
+///
+/// Example
+/// ```
+/// use lightgbm::Booster;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let bst = Arc::new(Booster::from_file(&"./test/test_from_file.input").unwrap());
+/// let workers = (0..4)
+///     .map(|i| {
+///         let bst = Arc::clone(&bst);
+///         thread::spawn(move || bst.predict_mat(vec![vec![i as f64 / 4.0; 28]]).unwrap())
+///     })
+///     .collect::<Vec<_>>();
+/// for worker in workers {
+///     assert_eq!(worker.join().unwrap().num_rows(), 1);
+/// }
+/// ```
 pub struct Booster {
     handle: lightgbm_sys::BoosterHandle,
+    /// Kept alive because LightGBM references the training data after the booster is created.
+    /// Boosters created by a search share it, as LightGBM only reads it.
+    train_data: Option<Arc<Dataset>>,
+    valid_data: Vec<Arc<Dataset>>,
+    categorical_encoder: Option<CategoricalEncoder>,
 }
 
 impl Booster {
-    fn new(handle: lightgbm_sys::BoosterHandle) -> Self {
-        Booster { handle }
+    fn new(handle: lightgbm_sys::BoosterHandle, train_data: Option<Arc<Dataset>>) -> Self {
+        let categorical_encoder = train_data
+            .as_ref()
+            .and_then(|dataset| dataset.categorical_encoder.clone());
+        Booster {
+            handle,
+            train_data,
+            valid_data: Vec::new(),
+            categorical_encoder,
+        }
     }
 
     /// Init from model file.
+    ///
+    /// A [`CategoricalEncoder`] saved with the model is restored as well.
     pub fn from_file(filename: &str) -> Result<Self> {
-        let filename_str = CString::new(filename).unwrap();
+        let filename_str = CString::new(filename)?;
         let mut out_num_iterations = 0;
         let mut handle = std::ptr::null_mut();
         lgbm_call!(lightgbm_sys::LGBM_BoosterCreateFromModelfile(
@@ -29,11 +93,15 @@ impl Booster {
             &mut handle
         ))?;
 
-        Ok(Booster::new(handle))
+        let mut booster = Booster::new(handle, None);
+        booster.categorical_encoder = CategoricalEncoder::read_model_file(filename)?;
+        Ok(booster)
     }
 
     /// Create a new Booster model with given Dataset and parameters.
     ///
+    /// Trains `num_iterations` iterations, or fewer when LightGBM cannot split any further.
+    ///
     /// Example
     /// ```
     /// extern crate serde_json;
@@ -57,22 +125,70 @@ impl Booster {
     /// let bst = Booster::train(dataset, &params).unwrap();
     /// ```
     pub fn train(dataset: Dataset, parameter: &Value) -> Result<Self> {
-        // get num_iterations
-        let num_iterations: i64 = if parameter["num_iterations"].is_null() {
-            100
+        let num_iterations = Self::num_iterations(parameter)?;
+        let mut booster = Self::create(dataset, parameter)?;
+        for _ in 0..num_iterations {
+            if booster.update_one_iter()? {
+                break;
+            }
+        }
+        Ok(booster)
+    }
+
+    /// Number of boosting iterations of the parameters, 100 by default.
+    pub(crate) fn num_iterations(parameter: &Value) -> Result<i64> {
+        if parameter["num_iterations"].is_null() {
+            Ok(100)
         } else {
-            parameter["num_iterations"].as_i64().unwrap()
-        };
+            parameter["num_iterations"].as_i64().ok_or_else(|| {
+                Error::invalid_parameter(format!(
+                    "num_iterations must be an integer, got {}",
+                    parameter["num_iterations"]
+                ))
+            })
+        }
+    }
 
+    /// Create a Booster for the given Dataset and parameters, without training it.
+    ///
+    /// Unlike [`Booster::train`], this allows adding validation data before training and
+    /// deciding after every [`Booster::update_one_iter`] whether to go on, e.g. for early
+    /// stopping.
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// let params = json!{{"objective": "binary", "metric": "auc"}};
+    /// let mut bst = Booster::create(train, &params).unwrap();
+    /// bst.add_valid_data(valid).unwrap();
+    /// for iteration in 0..3 {
+    ///     if bst.update_one_iter().unwrap() {
+    ///         break;
+    ///     }
+    ///     println!("[{}] auc: {}", iteration + 1, bst.eval(1).unwrap()[0]);
+    /// }
+    /// ```
+    pub fn create(dataset: Dataset, parameter: &Value) -> Result<Self> {
+        Self::create_shared(Arc::new(dataset), parameter)
+    }
+
+    /// Create a Booster training on a `Dataset` that other boosters may train on at the same
+    /// time.
+    pub(crate) fn create_shared(dataset: Arc<Dataset>, parameter: &Value) -> Result<Self> {
         // exchange params {"x": "y", "z": 1} => "x=y z=1"
         let params_string = parameter
             .as_object()
-            .unwrap()
+            .ok_or_else(|| Error::invalid_parameter("parameters must be a JSON object"))?
             .iter()
             .map(|(k, v)| format!("{}={}", k, v))
             .collect::<Vec<_>>()
             .join(" ");
-        let params_cstring = CString::new(params_string).unwrap();
+        let params_cstring = CString::new(params_string)?;
 
         let mut handle = std::ptr::null_mut();
         lgbm_call!(lightgbm_sys::LGBM_BoosterCreate(
@@ -80,19 +196,136 @@ impl Booster {
             params_cstring.as_ptr() as *const c_char,
             &mut handle
         ))?;
+        Ok(Booster::new(handle, Some(dataset)))
+    }
 
+    /// Run one boosting iteration, returning whether training cannot improve any further.
+    pub fn update_one_iter(&mut self) -> Result<bool> {
         let mut is_finished: i32 = 0;
-        for _ in 1..num_iterations {
-            lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
-                handle,
-                &mut is_finished
-            ))?;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterUpdateOneIter(
+            self.handle,
+            &mut is_finished
+        ))?;
+        Ok(is_finished == 1)
+    }
+
+    /// Remove the trees of the last boosting iteration.
+    pub fn rollback_one_iter(&mut self) -> Result<()> {
+        lgbm_call!(lightgbm_sys::LGBM_BoosterRollbackOneIter(self.handle))?;
+        Ok(())
+    }
+
+    /// Add a validation `Dataset`, evaluated with the metrics of the training parameters.
+    ///
+    /// The dataset must share the bin mappers of the training data, i.e. it must be created
+    /// with [`Dataset::from_mat_with_reference`] or [`Dataset::from_file_with_reference`]
+    /// using the training data as reference.
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    ///
+    /// let train = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// let params = json!{{"num_iterations": 3, "objective": "binary", "metric": "auc"}};
+    /// let mut bst = Booster::train(train, &params).unwrap();
+    /// bst.add_valid_data(valid).unwrap();
+    /// let auc = bst.eval(1).unwrap();
+    /// ```
+    pub fn add_valid_data(&mut self, dataset: Dataset) -> Result<()> {
+        self.add_shared_valid_data(Arc::new(dataset))
+    }
+
+    /// Add a validation `Dataset` that other boosters may evaluate at the same time.
+    pub(crate) fn add_shared_valid_data(&mut self, dataset: Arc<Dataset>) -> Result<()> {
+        let train_data = self.train_data.as_ref().ok_or_else(|| {
+            Error::invalid_input("cannot add validation data to a booster loaded from a model")
+        })?;
+        if !dataset.shares_bin_mappers_with(train_data) {
+            return Err(Error::invalid_input(
+                "validation data must be created with the training data as reference",
+            ));
         }
-        Ok(Booster::new(handle))
+        lgbm_call!(lightgbm_sys::LGBM_BoosterAddValidData(
+            self.handle,
+            dataset.handle
+        ))?;
+        self.valid_data.push(dataset);
+        Ok(())
+    }
+
+    /// Get the names of the evaluation metrics, in the order returned by [`Booster::eval`].
+    pub fn eval_names(&self) -> Result<Vec<String>> {
+        read_string_array(|len, out_len, buffer_len, out_buffer_len, out_strs| {
+            lgbm_call!(lightgbm_sys::LGBM_BoosterGetEvalNames(
+                self.handle,
+                len,
+                out_len,
+                buffer_len,
+                out_buffer_len,
+                out_strs
+            ))
+        })
+    }
+
+    /// Evaluate the metrics on the training data (`data_idx` 0) or on a validation `Dataset`
+    /// (`data_idx` 1, 2, ... in the order they were added).
+    pub fn eval(&self, data_idx: usize) -> Result<Vec<f64>> {
+        if self.train_data.is_none() || data_idx > self.valid_data.len() {
+            return Err(Error::invalid_input(format!(
+                "no evaluation data at index {}",
+                data_idx
+            )));
+        }
+
+        let mut num_eval = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetEvalCounts(
+            self.handle,
+            &mut num_eval
+        ))?;
+
+        let mut out_len = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_eval as usize];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetEval(
+            self.handle,
+            data_idx as i32,
+            &mut out_len,
+            out_result.as_mut_ptr() as *mut c_double
+        ))?;
+        out_result.truncate(out_len as usize);
+        Ok(out_result)
+    }
+
+    /// Predict results for given rows, with one row of outputs per input row.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Booster;
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// let data = vec![vec![0.5; 28], vec![0.1; 28], vec![0.9; 28]];
+    /// let prediction = bst.predict_mat(data).unwrap();
+    /// assert_eq!(prediction.num_rows(), 3);
+    /// let probability = prediction.row(0)[0];
+    /// ```
+    pub fn predict_mat(&self, data: Vec<Vec<f64>>) -> Result<Prediction> {
+        let (flat_data, data_length, feature_length) = flatten_rows(data)?;
+        let matrix = MatrixView::new(
+            &flat_data,
+            data_length,
+            feature_length,
+            MatrixLayout::RowMajor,
+        )?;
+        self.predict_slice(matrix)
     }
 
     /// Predict results for given data.
     ///
+    /// Single-output models return one inner vector holding every row, multiclass models one
+    /// inner vector per row.
+    ///
     /// Input data example
     /// ```
     /// let data = vec![vec![1.0, 0.1, 0.2],
@@ -104,36 +337,13 @@ impl Booster {
     /// ```
     /// let output = vec![vec![1.0, 0.109, 0.433]];
     /// ```
+    #[deprecated(
+        since = "0.3.0",
+        note = "the output shape depends on the objective, use `Booster::predict_mat` instead"
+    )]
     pub fn predict(&self, data: Vec<Vec<f64>>) -> Result<Vec<Vec<f64>>> {
-        let data_length = data.len();
-        let feature_length = data[0].len();
-        let params = CString::new("").unwrap();
-        let mut out_length: c_longlong = 0;
-        let flat_data = data.into_iter().flatten().collect::<Vec<_>>();
-
-        // get num_class
-        let mut num_class = 0;
-        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumClasses(
-            self.handle,
-            &mut num_class
-        ))?;
-
-        let out_result: Vec<f64> = vec![Default::default(); data_length * num_class as usize];
-
-        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMat(
-            self.handle,
-            flat_data.as_ptr() as *const c_void,
-            lightgbm_sys::C_API_DTYPE_FLOAT64 as i32,
-            data_length as i32,
-            feature_length as i32,
-            1_i32,
-            0_i32,
-            0_i32,
-            -1_i32,
-            params.as_ptr() as *const c_char,
-            &mut out_length,
-            out_result.as_ptr() as *mut c_double
-        ))?;
+        let out_result = self.predict_mat(data)?.into_vec();
+        let num_class = self.num_class()?;
 
         // reshape for multiclass [1,2,3,4,5,6] -> [[1,2,3], [4,5,6]]  # 3 class
         let reshaped_output = if num_class > 1 {
@@ -147,6 +357,412 @@ impl Booster {
         Ok(reshaped_output)
     }
 
+    /// Predict results for a dense matrix borrowed from a contiguous `f32` or `f64` slice,
+    /// without copying it.
+    ///
+    /// The result has one row of outputs per input row, as in [`Booster::predict_mat`].
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Booster, MatrixLayout, MatrixView};
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// let data = vec![0.5_f32; 28 * 2];
+    /// let matrix = MatrixView::new(&data, 2, 28, MatrixLayout::RowMajor).unwrap();
+    /// let prediction = bst.predict_slice(matrix).unwrap();
+    /// assert_eq!(prediction.num_rows(), 2);
+    /// ```
+    pub fn predict_slice<T: DType>(&self, data: MatrixView<T>) -> Result<Prediction> {
+        self.predict_with_params(data, &PredictParameters::default())
+    }
+
+    /// Predict results for a dense matrix with the given prediction parameters.
+    ///
+    /// See [`PredictParameters`] for an example.
+    pub fn predict_with_params<T: DType>(
+        &self,
+        data: MatrixView<T>,
+        params: &PredictParameters,
+    ) -> Result<Prediction> {
+        Ok(Prediction::new(
+            self.predict_matrix(data, params)?,
+            data.num_rows(),
+        ))
+    }
+
+    /// Predict values of the given type for a large dense matrix, split into `num_workers`
+    /// chunks of rows scored concurrently.
+    ///
+    /// Each chunk is scored by LightGBM with `num_threads` OpenMP threads, or LightGBM's default
+    /// (all cores) when 0, so up to `num_workers * num_threads` threads run at once. A
+    /// column-major matrix is copied to row-major order first.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Booster, MatrixLayout, MatrixView, PredictType};
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// let data = vec![0.5_f64; 28 * 1000];
+    /// let matrix = MatrixView::new(&data, 1000, 28, MatrixLayout::RowMajor).unwrap();
+    /// let prediction = bst.predict_parallel(matrix, PredictType::Normal, 4, 1).unwrap();
+    /// assert_eq!(prediction.num_rows(), 1000);
+    /// ```
+    pub fn predict_parallel<T: DType>(
+        &self,
+        data: MatrixView<T>,
+        predict_type: PredictType,
+        num_workers: usize,
+        num_threads: usize,
+    ) -> Result<Prediction> {
+        let params = PredictParametersBuilder::default()
+            .predict_type(predict_type)
+            .num_threads(num_threads)
+            .build()
+            .map_err(Error::invalid_parameter)?;
+        self.predict_parallel_with_params(data, &params, num_workers)
+    }
+
+    /// Predict results for a large dense matrix with the given prediction parameters, split
+    /// into `num_workers` chunks of rows scored concurrently like [`Booster::predict_parallel`].
+    ///
+    /// Each chunk is scored with the `num_threads` OpenMP threads of `params`.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Booster, MatrixLayout, MatrixView, PredictParametersBuilder};
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// let data = vec![0.5_f64; 28 * 1000];
+    /// let matrix = MatrixView::new(&data, 1000, 28, MatrixLayout::RowMajor).unwrap();
+    /// let params = PredictParametersBuilder::default().num_threads(1).build().unwrap();
+    /// let prediction = bst.predict_parallel_with_params(matrix, &params, 4).unwrap();
+    /// assert_eq!(prediction.num_rows(), 1000);
+    /// ```
+    pub fn predict_parallel_with_params<T: DType>(
+        &self,
+        data: MatrixView<T>,
+        params: &PredictParameters,
+        num_workers: usize,
+    ) -> Result<Prediction> {
+        let (num_rows, num_cols) = (data.num_rows(), data.num_cols());
+        let num_workers = num_workers.clamp(1, num_rows.max(1));
+        if num_workers == 1 || num_cols == 0 {
+            return self.predict_with_params(data, params);
+        }
+
+        let values = data.row_major_values();
+        let chunk_len = num_rows.div_ceil(num_workers) * num_cols;
+        let outputs = thread::scope(|scope| {
+            let workers = values
+                .chunks(chunk_len)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let matrix = MatrixView::new(
+                            chunk,
+                            chunk.len() / num_cols,
+                            num_cols,
+                            MatrixLayout::RowMajor,
+                        )?;
+                        self.predict_matrix(matrix, params)
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        Ok(Prediction::new(outputs.concat(), num_rows))
+    }
+
+    /// Predict results for the rows of a CSV, TSV or LibSVM file and write them to
+    /// `result_filename`, one line per row, without parsing the data in Rust.
+    ///
+    /// The format is detected by LightGBM as when loading a `Dataset`. A label column at the
+    /// position used for training is detected and ignored.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Booster, PredictParameters};
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// bst.predict_file(
+    ///     "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+    ///     false,
+    ///     "binary.test.prediction",
+    ///     &PredictParameters::default(),
+    /// )
+    /// .unwrap();
+    /// # std::fs::remove_file("binary.test.prediction").unwrap();
+    /// ```
+    pub fn predict_file(
+        &self,
+        data_filename: &str,
+        data_has_header: bool,
+        result_filename: &str,
+        params: &PredictParameters,
+    ) -> Result<()> {
+        let data_filename_str = CString::new(data_filename)?;
+        let result_filename_str = CString::new(result_filename)?;
+        let parameter = CString::new(params.to_predict_string()?)?;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForFile(
+            self.handle,
+            data_filename_str.as_ptr() as *const c_char,
+            data_has_header as i32,
+            params.predict_type().to_c_api(),
+            params.start_iteration(),
+            params.num_iteration(),
+            parameter.as_ptr() as *const c_char,
+            result_filename_str.as_ptr() as *const c_char
+        ))?;
+        Ok(())
+    }
+
+    /// Predict values for a dense matrix, flat in row-major order.
+    fn predict_matrix<T: DType>(
+        &self,
+        data: MatrixView<T>,
+        params: &PredictParameters,
+    ) -> Result<Vec<f64>> {
+        let parameter = CString::new(params.to_predict_string()?)?;
+        let predict_type = params.predict_type().to_c_api();
+        let mut num_predict = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterCalcNumPredict(
+            self.handle,
+            data.num_rows() as i32,
+            predict_type,
+            params.start_iteration(),
+            params.num_iteration(),
+            &mut num_predict
+        ))?;
+
+        let mut out_length: c_longlong = 0;
+        let mut out_result: Vec<f64> = vec![Default::default(); num_predict as usize];
+        lgbm_call!(lightgbm_sys::LGBM_BoosterPredictForMat(
+            self.handle,
+            data.as_slice().as_ptr() as *const c_void,
+            T::C_API_DTYPE,
+            data.num_rows() as i32,
+            data.num_cols() as i32,
+            data.is_row_major(),
+            predict_type,
+            params.start_iteration(),
+            params.num_iteration(),
+            parameter.as_ptr() as *const c_char,
+            &mut out_length,
+            out_result.as_mut_ptr() as *mut c_double
+        ))?;
+        out_result.truncate(out_length as usize);
+        Ok(out_result)
+    }
+
+    /// Predict values of the given type for an ndarray matrix.
+    ///
+    /// The array is passed to LightGBM without copying when it is contiguous in C or Fortran
+    /// order. The output has one row per input row: `num_class` columns for
+    /// [`PredictType::Normal`] and [`PredictType::RawScore`], one column per tree for
+    /// [`PredictType::LeafIndex`] and `num_class * (num_feature + 1)` columns for
+    /// [`PredictType::Contrib`].
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "ndarray",
+        doc = r##"
+```
+extern crate ndarray;
+
+use lightgbm::{Booster, PredictType};
+use ndarray::Array2;
+
+let bst = Booster::from_file("./test/test_from_file.input").unwrap();
+let data = Array2::<f64>::zeros((3, 28));
+let output = bst.predict_array(data.view(), PredictType::Normal).unwrap();
+assert_eq!(output.dim(), (3, 1));
+```
+"##
+    )]
+    #[cfg(feature = "ndarray")]
+    pub fn predict_array<T: DType>(
+        &self,
+        data: ArrayView2<T>,
+        predict_type: PredictType,
+    ) -> Result<Array2<f64>> {
+        let num_rows = data.nrows();
+        let out_result = with_array_view(&data, |matrix| {
+            self.predict_matrix(matrix, &predict_type.into())
+        })?;
+        let num_cols = out_result.len().checked_div(num_rows).unwrap_or(0);
+        Array2::from_shape_vec((num_rows, num_cols), out_result)
+            .map_err(|e| Error::invalid_input(format!("unexpected prediction shape: {}", e)))
+    }
+
+    /// Predict values of the given type for an Arrow `RecordBatch`, with one row of outputs per
+    /// input row.
+    ///
+    /// Columns are selected by [`Booster::feature_name`], so their order does not matter and
+    /// extra columns are ignored. Columns are converted as in
+    /// [`Dataset::from_record_batch`](crate::Dataset::from_record_batch), encoding categories
+    /// with the booster's [`CategoricalEncoder`].
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    #[cfg(feature = "arrow")]
+    pub fn predict_record_batch(
+        &self,
+        batch: &RecordBatch,
+        predict_type: PredictType,
+    ) -> Result<Prediction> {
+        let feature_name = self.feature_name()?;
+        let data =
+            record_batch::feature_matrix(batch, &feature_name, self.categorical_encoder.as_ref())?;
+        let matrix = MatrixView::new(
+            &data,
+            batch.num_rows(),
+            feature_name.len(),
+            MatrixLayout::ColumnMajor,
+        )?;
+        self.predict_with_params(matrix, &predict_type.into())
+    }
+
+    /// Predict values of the given type for a polars DataFrame.
+    ///
+    /// Columns are selected by [`Booster::feature_name`], so their order does not matter and
+    /// extra columns are ignored. Columns are converted as in
+    /// [`Dataset::from_dataframe`](crate::Dataset::from_dataframe), encoding categories with the
+    /// booster's [`CategoricalEncoder`]. The result has one row per
+    /// input row and a `prediction` column, or `prediction_0`, `prediction_1`, ... columns when
+    /// there are several values per row.
+    ///
+    /// Note: the feature ```dataframe``` is required for this method
+    #[cfg(feature = "dataframe")]
+    pub fn predict_dataframe(
+        &self,
+        dataframe: &DataFrame,
+        predict_type: PredictType,
+    ) -> Result<DataFrame> {
+        let feature_name = self.feature_name()?;
+        let data =
+            dataframe::feature_matrix(dataframe, &feature_name, self.categorical_encoder.as_ref())?;
+        let matrix = MatrixView::new(
+            &data,
+            dataframe.height(),
+            feature_name.len(),
+            MatrixLayout::ColumnMajor,
+        )?;
+        let out_result = self.predict_matrix(matrix, &predict_type.into())?;
+        dataframe::prediction_frame(&out_result, dataframe.height())
+    }
+
+    /// Predict results for rows of string cells, encoded with the booster's
+    /// [`CategoricalEncoder`].
+    ///
+    /// The result is shaped as in [`Booster::predict_mat`].
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Booster, CategoricalEncoder, CategoricalFeature, Dataset};
+    /// use serde_json::json;
+    ///
+    /// let rows = vec![vec!["red", "1.0"], vec!["blue", "0.5"], vec!["red", "0.2"], vec!["blue", "0.9"]];
+    /// let encoder = CategoricalEncoder::fit(&rows, &[0]).unwrap();
+    /// let features = [CategoricalFeature::from(0)];
+    /// let dataset = Dataset::from_mat_with_categorical_feature(
+    ///     encoder.transform(&rows).unwrap(), vec![1.0, 0.0, 1.0, 0.0], &["color", "size"], &features
+    /// ).unwrap();
+    /// let bst = Booster::train(dataset, &json!({"num_iterations": 3, "objective": "binary"}))
+    ///     .unwrap()
+    ///     .with_categorical_encoder(encoder);
+    /// let result = bst.predict_strings(&[vec!["blue", "0.7"]]).unwrap();
+    /// ```
+    pub fn predict_strings<S: AsRef<str>>(&self, data: &[Vec<S>]) -> Result<Prediction> {
+        let encoder = self
+            .categorical_encoder
+            .as_ref()
+            .ok_or_else(|| Error::invalid_input("the booster has no categorical encoder"))?;
+        self.predict_mat(encoder.transform(data)?)
+    }
+
+    /// Predict results for rows keyed by feature name.
+    ///
+    /// Each row is any collection of `(name, value)` pairs, such as a `HashMap<String, f64>`.
+    /// Values are reordered to match [`Booster::feature_name`], and a row with a missing,
+    /// unknown or duplicated feature is rejected. The result is shaped as in
+    /// [`Booster::predict_mat`].
+    ///
+    /// Example
+    /// ```
+    /// extern crate serde_json;
+    /// use lightgbm::{Dataset, Booster};
+    /// use serde_json::json;
+    /// use std::collections::HashMap;
+    ///
+    /// let data = vec![vec![1.0, 0.1],
+    ///                vec![0.7, 0.4],
+    ///                vec![0.2, 0.8],
+    ///                vec![0.1, 0.9]];
+    /// let label = vec![0.0, 0.0, 1.0, 1.0];
+    /// let dataset = Dataset::from_mat_with_feature_names(data, label, &["x", "y"]).unwrap();
+    /// let bst = Booster::train(dataset, &json!({"num_iterations": 3, "objective": "binary"})).unwrap();
+    ///
+    /// let mut row = HashMap::new();
+    /// row.insert("y", 0.2);
+    /// row.insert("x", 0.9);
+    /// let result = bst.predict_by_name(vec![row]).unwrap();
+    /// ```
+    pub fn predict_by_name<R, K>(&self, data: Vec<R>) -> Result<Prediction>
+    where
+        R: IntoIterator<Item = (K, f64)>,
+        K: AsRef<str>,
+    {
+        let feature_name = self.feature_name()?;
+        let feature_index = feature_name
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut rows = Vec::with_capacity(data.len());
+        for (row_idx, named_row) in data.into_iter().enumerate() {
+            let mut row = vec![0.0; feature_name.len()];
+            let mut seen = vec![false; feature_name.len()];
+            for (name, value) in named_row {
+                let name = name.as_ref();
+                let idx = *feature_index.get(name).ok_or_else(|| {
+                    Error::invalid_input(format!("unknown feature '{}' in row {}", name, row_idx))
+                })?;
+                if seen[idx] {
+                    return Err(Error::invalid_input(format!(
+                        "duplicated feature '{}' in row {}",
+                        name, row_idx
+                    )));
+                }
+                row[idx] = value;
+                seen[idx] = true;
+            }
+            if let Some(idx) = seen.iter().position(|s| !s) {
+                return Err(Error::invalid_input(format!(
+                    "missing feature '{}' in row {}",
+                    feature_name[idx], row_idx
+                )));
+            }
+            rows.push(row);
+        }
+        self.predict_mat(rows)
+    }
+
+    /// Get the number of classes, 1 for models with a single output.
+    pub fn num_class(&self) -> Result<i32> {
+        let mut num_class = 0;
+        lgbm_call!(lightgbm_sys::LGBM_BoosterGetNumClasses(
+            self.handle,
+            &mut num_class
+        ))?;
+        Ok(num_class)
+    }
+
     /// Get Feature Num.
     pub fn num_feature(&self) -> Result<i32> {
         let mut out_len = 0;
@@ -159,30 +775,16 @@ impl Booster {
 
     /// Get Feature Names.
     pub fn feature_name(&self) -> Result<Vec<String>> {
-        let num_feature = self.num_feature()?;
-        let feature_name_length = 32;
-        let mut num_feature_names = 0;
-        let mut out_buffer_len = 0;
-        let out_strs = (0..num_feature)
-            .map(|_| {
-                CString::new(" ".repeat(feature_name_length))
-                    .unwrap()
-                    .into_raw() as *mut c_char
-            })
-            .collect::<Vec<_>>();
-        lgbm_call!(lightgbm_sys::LGBM_BoosterGetFeatureNames(
-            self.handle,
-            feature_name_length as i32,
-            &mut num_feature_names,
-            num_feature as u64,
-            &mut out_buffer_len,
-            out_strs.as_ptr() as *mut *mut c_char
-        ))?;
-        let output: Vec<String> = out_strs
-            .into_iter()
-            .map(|s| unsafe { CString::from_raw(s).into_string().unwrap() })
-            .collect();
-        Ok(output)
+        read_string_array(|len, out_len, buffer_len, out_buffer_len, out_strs| {
+            lgbm_call!(lightgbm_sys::LGBM_BoosterGetFeatureNames(
+                self.handle,
+                len,
+                out_len,
+                buffer_len,
+                out_buffer_len,
+                out_strs
+            ))
+        })
     }
 
     // Get Feature Importance
@@ -198,9 +800,25 @@ impl Booster {
         Ok(out_result)
     }
 
-    /// Save model to file.
+    /// Attach the [`CategoricalEncoder`] used to encode the training data, for
+    /// [`Booster::predict_strings`] and to be saved with the model.
+    ///
+    /// Boosters trained on a [`Dataset::from_dataframe`](crate::Dataset::from_dataframe) or
+    /// [`Dataset::from_record_batch`](crate::Dataset::from_record_batch) with categorical columns
+    /// already have the encoder of the dataset.
+    pub fn with_categorical_encoder(mut self, encoder: CategoricalEncoder) -> Self {
+        self.categorical_encoder = Some(encoder);
+        self
+    }
+
+    /// The [`CategoricalEncoder`] of the booster, if any.
+    pub fn categorical_encoder(&self) -> Option<&CategoricalEncoder> {
+        self.categorical_encoder.as_ref()
+    }
+
+    /// Save model to file, together with its [`CategoricalEncoder`] if any.
     pub fn save_file(&self, filename: &str) -> Result<()> {
-        let filename_str = CString::new(filename).unwrap();
+        let filename_str = CString::new(filename)?;
         lgbm_call!(lightgbm_sys::LGBM_BoosterSaveModel(
             self.handle,
             0_i32,
@@ -208,30 +826,71 @@ impl Booster {
             0_i32,
             filename_str.as_ptr() as *const c_char
         ))?;
+        if let Some(encoder) = &self.categorical_encoder {
+            encoder.save_to_model_file(filename)?;
+        }
         Ok(())
     }
+
+    /// Dump the model to JSON, including the structure of every tree.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Booster;
+    ///
+    /// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+    /// let model = bst.dump_model().unwrap();
+    /// println!("{} trees", model["tree_info"].as_array().unwrap().len());
+    /// ```
+    pub fn dump_model(&self) -> Result<Value> {
+        let mut out_len: i64 = 0;
+        let mut buffer = vec![0_u8; 1];
+        // The first call only reports the length when the buffer is too small.
+        loop {
+            lgbm_call!(lightgbm_sys::LGBM_BoosterDumpModel(
+                self.handle,
+                0_i32,
+                -1_i32,
+                0_i32,
+                buffer.len() as i64,
+                &mut out_len,
+                buffer.as_mut_ptr() as *mut c_char
+            ))?;
+            if out_len as usize <= buffer.len() {
+                break;
+            }
+            buffer.resize(out_len as usize, 0);
+        }
+        let json = std::str::from_utf8(&buffer[..(out_len as usize).saturating_sub(1)])?;
+        serde_json::from_str(json)
+            .map_err(|e| Error::invalid_input(format!("cannot parse the model dump: {}", e)))
+    }
 }
 
 impl Drop for Booster {
     fn drop(&mut self) {
-        lgbm_call!(lightgbm_sys::LGBM_BoosterFree(self.handle)).unwrap();
+        // Freeing cannot fail for a valid handle, and a destructor cannot report an error.
+        let _ = lgbm_call!(lightgbm_sys::LGBM_BoosterFree(self.handle));
     }
 }
 
+// LightGBM locks the booster internally (shared for prediction, exclusive for training), and the
+// methods changing the model take `&mut self`.
+unsafe impl Send for Booster {}
+unsafe impl Sync for Booster {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dataset::tests::read_train_file;
+    use crate::CategoricalFeature;
     use serde_json::json;
     use std::fs;
     use std::path::Path;
 
-    fn _read_train_file() -> Result<Dataset> {
-        Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
-    }
-
     fn _train_booster(params: &Value) -> Booster {
-        let dataset = _read_train_file().unwrap();
-        Booster::train(dataset, &params).unwrap()
+        let dataset = read_train_file().unwrap();
+        Booster::train(dataset, params).unwrap()
     }
 
     fn _default_params() -> Value {
@@ -258,14 +917,606 @@ mod tests {
         };
         let bst = _train_booster(&params);
         let feature = vec![vec![0.5; 28], vec![0.0; 28], vec![0.9; 28]];
-        let result = bst.predict(feature).unwrap();
-        let mut normalized_result = Vec::new();
-        for r in &result[0] {
-            normalized_result.push(if r > &0.5 { 1 } else { 0 });
-        }
+        let result = bst.predict_mat(feature).unwrap();
+        assert_eq!(result.num_rows(), 3);
+        assert_eq!(result.num_outputs(), 1);
+        let normalized_result = result
+            .rows()
+            .map(|r| if r[0] > 0.5 { 1 } else { 0 })
+            .collect::<Vec<_>>();
         assert_eq!(normalized_result, vec![0, 0, 1]);
     }
 
+    #[test]
+    fn predict_shapes() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+            vec![0.2, 0.2, 0.8],
+            vec![0.1, 0.7, 1.0],
+            vec![0.3, 0.9, 0.1],
+        ];
+        let label = vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+        for (objective, num_outputs) in &[
+            ("regression", 1),
+            ("binary", 1),
+            ("multiclass", 3),
+            ("multiclassova", 3),
+        ] {
+            let label = if *objective == "binary" {
+                label.iter().map(|l| (*l > 0.0) as i32 as f32).collect()
+            } else {
+                label.clone()
+            };
+            let params = json! {
+                {
+                    "num_iterations": 3,
+                    "objective": objective,
+                    "num_class": if *num_outputs > 1 { 3 } else { 1 },
+                    "min_data_in_leaf": 1
+                }
+            };
+            let dataset = Dataset::from_mat(data.clone(), label).unwrap();
+            let bst = Booster::train(dataset, &params).unwrap();
+            let result = bst.predict_mat(data[..2].to_vec()).unwrap();
+            assert_eq!(result.num_rows(), 2);
+            assert_eq!(result.num_outputs(), *num_outputs);
+            assert_eq!(result.rows().count(), 2);
+            assert_eq!(result.row(1).len(), *num_outputs);
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let result = Booster::train(read_train_file().unwrap(), &json!([1, 2]));
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+        let result = Booster::train(
+            read_train_file().unwrap(),
+            &json!({"num_iterations": "ten"}),
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(_))));
+        let result = Booster::train(
+            read_train_file().unwrap(),
+            &json!({"num_iterations": 1, "objective": "unknown"}),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::LightGBM {
+                call: "LGBM_BoosterCreate",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Booster::from_file("model\0.txt"),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn predict_deprecated_shape() {
+        let bst = _train_booster(&_default_params());
+        let rows = vec![vec![0.5; 28], vec![0.0; 28]];
+        let result = bst.predict(rows.clone()).unwrap();
+        assert_eq!(result, vec![bst.predict_mat(rows).unwrap().into_vec()]);
+    }
+
+    #[test]
+    fn predict_slice() {
+        let bst = _train_booster(&_default_params());
+        let rows = vec![vec![0.5; 28], vec![0.0; 28], vec![0.9; 28]];
+        let expected = bst.predict_mat(rows.clone()).unwrap();
+
+        let row_major = rows.iter().flatten().cloned().collect::<Vec<f64>>();
+        let matrix = MatrixView::new(&row_major, 3, 28, MatrixLayout::RowMajor).unwrap();
+        assert_eq!(bst.predict_slice(matrix).unwrap(), expected);
+
+        let col_major = (0..28)
+            .flat_map(|col| rows.iter().map(move |row| row[col] as f32))
+            .collect::<Vec<f32>>();
+        let matrix = MatrixView::new(&col_major, 3, 28, MatrixLayout::ColumnMajor).unwrap();
+        assert_eq!(bst.predict_slice(matrix).unwrap(), expected);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn predict_array() {
+        use ndarray::{Array2, ShapeBuilder};
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "multiclass",
+                "num_class": 3,
+                "min_data_in_leaf": 1
+            }
+        };
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+            vec![0.2, 0.2, 0.8],
+            vec![0.1, 0.7, 1.0],
+            vec![0.3, 0.9, 0.1],
+        ];
+        let label = vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0];
+        let bst = Booster::train(Dataset::from_mat(data, label).unwrap(), &params).unwrap();
+
+        let c_order = Array2::<f64>::from_elem((4, 3), 0.5);
+        let output = bst
+            .predict_array(c_order.view(), PredictType::Normal)
+            .unwrap();
+        assert_eq!(output.dim(), (4, 3));
+
+        let f_order = Array2::<f32>::from_elem((4, 3).f(), 0.5);
+        let raw = bst
+            .predict_array(f_order.view(), PredictType::RawScore)
+            .unwrap();
+        assert_eq!(raw.dim(), (4, 3));
+
+        let transposed = Array2::<f64>::from_elem((3, 4), 0.5);
+        let contrib = bst
+            .predict_array(transposed.t(), PredictType::Contrib)
+            .unwrap();
+        assert_eq!(contrib.dim(), (4, 3 * (3 + 1)));
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn predict_record_batch() {
+        use arrow::array::{ArrayRef, Float64Array};
+        use std::sync::Arc;
+
+        let data = vec![
+            vec![1.0, 0.1],
+            vec![0.7, 0.4],
+            vec![0.9, 0.8],
+            vec![0.2, 0.2],
+            vec![0.1, 0.7],
+        ];
+        let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
+        let dataset = Dataset::from_mat_with_feature_names(data, label, &["a", "b"]).unwrap();
+        let bst = Booster::train(dataset, &_default_params()).unwrap();
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Float64Array::from(vec![1.0, 2.0])) as ArrayRef,
+            ),
+            (
+                "b",
+                Arc::new(Float64Array::from(vec![0.8, 0.1])) as ArrayRef,
+            ),
+            (
+                "a",
+                Arc::new(Float64Array::from(vec![0.9, 0.2])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        assert_eq!(
+            bst.predict_record_batch(&batch, PredictType::Normal)
+                .unwrap(),
+            bst.predict_mat(vec![vec![0.9, 0.8], vec![0.2, 0.1]])
+                .unwrap()
+        );
+
+        let batch = batch.project(&[0, 1]).unwrap();
+        assert!(bst
+            .predict_record_batch(&batch, PredictType::Normal)
+            .is_err());
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn predict_record_batch_dictionary_in_another_order() {
+        use arrow::array::{ArrayRef, DictionaryArray, Float64Array, Int32Array, StringArray};
+        use arrow::datatypes::Int32Type;
+        use std::sync::Arc;
+
+        let colors = ["red", "green", "blue"];
+        let color: DictionaryArray<Int32Type> = (0..300).map(|i| colors[i % 3]).collect();
+        let size = Float64Array::from((0..300).map(|i| (i % 10) as f64 / 10.0).collect::<Vec<_>>());
+        let label = Float64Array::from(
+            (0..300)
+                .map(|i| if i % 3 == 1 { 1.0 } else { 0.0 })
+                .collect::<Vec<_>>(),
+        );
+        let train = RecordBatch::try_from_iter(vec![
+            ("color", Arc::new(color) as ArrayRef),
+            ("size", Arc::new(size) as ArrayRef),
+            ("label", Arc::new(label) as ArrayRef),
+        ])
+        .unwrap();
+        let dataset = Dataset::from_record_batch(&train, "label").unwrap();
+        let params = json! {
+            {
+                "num_iterations": 5,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_per_group": 1,
+                "data_random_seed": 0
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+        assert_eq!(
+            bst.categorical_encoder().unwrap().categories(),
+            &[vec!["red", "green", "blue"]]
+        );
+
+        // The dictionary lists blue, green and red, against red, green and blue in training.
+        let values = StringArray::from(vec!["blue", "green", "red"]);
+        let keys = Int32Array::from(vec![2, 1, 0]);
+        let color = DictionaryArray::<Int32Type>::try_new(keys, Arc::new(values)).unwrap();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("color", Arc::new(color) as ArrayRef),
+            (
+                "size",
+                Arc::new(Float64Array::from(vec![0.5, 0.5, 0.5])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let result = bst
+            .predict_record_batch(&batch, PredictType::Normal)
+            .unwrap();
+        let expected = bst
+            .predict_mat(vec![vec![0.0, 0.5], vec![1.0, 0.5], vec![2.0, 0.5]])
+            .unwrap();
+        assert_eq!(result, expected);
+        assert!(result.row(1)[0] > 0.5);
+    }
+
+    #[cfg(feature = "dataframe")]
+    #[test]
+    fn predict_dataframe() {
+        use polars::df;
+
+        let data = vec![
+            vec![1.0, 0.1],
+            vec![0.7, 0.4],
+            vec![0.9, 0.8],
+            vec![0.2, 0.2],
+            vec![0.1, 0.7],
+        ];
+        let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
+        let dataset = Dataset::from_mat_with_feature_names(data, label, &["a", "b"]).unwrap();
+        let bst = Booster::train(dataset, &_default_params()).unwrap();
+
+        let df = df![
+            "id" => [1, 2],
+            "b" => [Some(0.8), None],
+            "a" => [0.9, 0.2]
+        ]
+        .unwrap();
+        let result = bst.predict_dataframe(&df, PredictType::Normal).unwrap();
+        assert_eq!(result.get_column_names(), &["prediction"]);
+        let expected = bst
+            .predict_mat(vec![vec![0.9, 0.8], vec![0.2, f64::NAN]])
+            .unwrap();
+        let predictions = result.column("prediction").unwrap().f64().unwrap();
+        assert_eq!(predictions.get(0), Some(expected.row(0)[0]));
+        assert_eq!(predictions.get(1), Some(expected.row(1)[0]));
+
+        let df = df!["a" => [0.9, 0.2]].unwrap();
+        assert!(bst.predict_dataframe(&df, PredictType::Normal).is_err());
+    }
+
+    #[cfg(feature = "dataframe")]
+    #[test]
+    fn predict_dataframe_categories_in_another_order() {
+        use polars::df;
+        use polars::prelude::{CategoricalOrdering, DataType, NamedFrom, Series};
+
+        let filename = "./test/test_predict_dataframe_categories.output";
+        let colors = ["red", "green", "blue"];
+        let train = df![
+            "color" => (0..300).map(|i| colors[i % 3]).collect::<Vec<_>>(),
+            "size" => (0..300).map(|i| (i % 10) as f64 / 10.0).collect::<Vec<_>>(),
+            "label" => (0..300).map(|i| if i % 3 == 1 { 1.0 } else { 0.0 }).collect::<Vec<_>>()
+        ]
+        .unwrap();
+        let dataset = Dataset::from_dataframe(train, String::from("label")).unwrap();
+        let params = json! {
+            {
+                "num_iterations": 5,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_per_group": 1,
+                "data_random_seed": 0
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+        let encoder = bst.categorical_encoder().unwrap().clone();
+        assert_eq!(encoder.categories(), &[vec!["red", "green", "blue"]]);
+
+        // The categories of this column get physical codes in the order blue, green, red.
+        let color = Series::new("color".into(), ["blue", "green", "red"])
+            .cast(&DataType::Categorical(None, CategoricalOrdering::Physical))
+            .unwrap();
+        let size = Series::new("size".into(), [0.5, 0.5, 0.5]);
+        let df = DataFrame::new(vec![color.into(), size.into()]).unwrap();
+        let expected = bst
+            .predict_mat(vec![vec![2.0, 0.5], vec![1.0, 0.5], vec![0.0, 0.5]])
+            .unwrap();
+        let result = bst.predict_dataframe(&df, PredictType::Normal).unwrap();
+        let predictions = result.column("prediction").unwrap().f64().unwrap();
+        for (i, row) in expected.rows().enumerate() {
+            assert_eq!(predictions.get(i), Some(row[0]));
+        }
+        assert!(predictions.get(1).unwrap() > 0.5);
+
+        bst.save_file(filename).unwrap();
+        let loaded = Booster::from_file(filename).unwrap();
+        assert_eq!(loaded.categorical_encoder(), Some(&encoder));
+        assert_eq!(
+            loaded.predict_dataframe(&df, PredictType::Normal).unwrap(),
+            result
+        );
+        let _ = fs::remove_file(filename);
+    }
+
+    #[test]
+    fn predict_by_name() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+            vec![0.2, 0.2, 0.8],
+            vec![0.1, 0.7, 1.0],
+        ];
+        let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
+        let dataset = Dataset::from_mat_with_feature_names(data, label, &["a", "b", "c"]).unwrap();
+        let bst = Booster::train(dataset, &_default_params()).unwrap();
+
+        let row = vec![("c", 0.5), ("a", 0.9), ("b", 0.8)];
+        assert_eq!(
+            bst.predict_by_name(vec![row]).unwrap(),
+            bst.predict_mat(vec![vec![0.9, 0.8, 0.5]]).unwrap()
+        );
+
+        let mut row = HashMap::new();
+        row.insert(String::from("a"), 0.9);
+        row.insert(String::from("b"), 0.8);
+        assert!(bst.predict_by_name(vec![row.clone()]).is_err());
+        row.insert(String::from("c"), 0.5);
+        row.insert(String::from("d"), 0.1);
+        assert!(bst.predict_by_name(vec![row]).is_err());
+    }
+
+    #[test]
+    fn valid_data() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        let mut bst = Booster::train(train, &_default_params()).unwrap();
+        bst.add_valid_data(valid).unwrap();
+        assert_eq!(bst.eval_names().unwrap(), vec!["auc"]);
+        assert_eq!(bst.eval(1).unwrap().len(), 1);
+        assert!(bst.eval(2).is_err());
+
+        let unrelated =
+            Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.test")
+                .unwrap();
+        assert!(bst.add_valid_data(unrelated).is_err());
+    }
+
+    #[test]
+    fn concurrent_predict() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Booster>();
+        assert_send_sync::<Dataset>();
+
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        let params = json! {{"num_iterations": 10, "objective": "binary", "metric": "auc"}};
+        let mut bst = Booster::train(train, &params).unwrap();
+        bst.add_valid_data(valid).unwrap();
+
+        let rows = (0..64)
+            .map(|i| vec![i as f64 / 64.0; 28])
+            .collect::<Vec<_>>();
+        let expected = bst.predict_mat(rows.clone()).unwrap();
+        let expected_auc = bst.eval(1).unwrap();
+        std::thread::scope(|scope| {
+            for worker in 0..8 {
+                let (bst, rows, expected, expected_auc) = (&bst, &rows, &expected, &expected_auc);
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        if (worker + i) % 5 == 0 {
+                            assert_eq!(&bst.eval(1).unwrap(), expected_auc);
+                        }
+                        assert_eq!(&bst.predict_mat(rows.clone()).unwrap(), expected);
+                    }
+                });
+            }
+        });
+
+        // training again takes `&mut self`, then the model can move to another thread
+        bst.update_one_iter().unwrap();
+        let expected = bst.predict_mat(rows.clone()).unwrap();
+        let handle = std::thread::spawn(move || bst.predict_mat(rows).unwrap());
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    #[test]
+    fn predict_parallel() {
+        let bst = _train_booster(&_default_params());
+        let rows = (0..101)
+            .map(|i| {
+                (0..28)
+                    .map(|j| ((i * j) % 7) as f64 / 7.0)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected = bst.predict_mat(rows.clone()).unwrap();
+
+        let row_major = rows.iter().flatten().cloned().collect::<Vec<f64>>();
+        let matrix = MatrixView::new(&row_major, 101, 28, MatrixLayout::RowMajor).unwrap();
+        for &(num_workers, num_threads) in &[(1, 0), (4, 1), (8, 2), (200, 1)] {
+            let prediction = bst
+                .predict_parallel(matrix, PredictType::Normal, num_workers, num_threads)
+                .unwrap();
+            assert_eq!(prediction, expected);
+        }
+
+        let col_major = (0..28)
+            .flat_map(|col| rows.iter().map(move |row| row[col]))
+            .collect::<Vec<f64>>();
+        let matrix = MatrixView::new(&col_major, 101, 28, MatrixLayout::ColumnMajor).unwrap();
+        let prediction = bst
+            .predict_parallel(matrix, PredictType::RawScore, 3, 1)
+            .unwrap();
+        assert_eq!(prediction.num_rows(), 101);
+
+        let empty = MatrixView::<f64>::new(&[], 0, 28, MatrixLayout::RowMajor).unwrap();
+        let prediction = bst
+            .predict_parallel(empty, PredictType::Normal, 4, 1)
+            .unwrap();
+        assert_eq!(prediction.num_rows(), 0);
+    }
+
+    #[test]
+    fn predict_parallel_with_params() {
+        let bst = _train_booster(&_default_params());
+        let rows = (0..101)
+            .flat_map(|i| (0..28).map(move |j| ((i + j) % 5) as f64 / 5.0))
+            .collect::<Vec<f64>>();
+        let matrix = MatrixView::new(&rows, 101, 28, MatrixLayout::RowMajor).unwrap();
+        for &num_workers in &[1, 3, 8] {
+            for &predict_type in &[PredictType::Normal, PredictType::LeafIndex] {
+                let params = PredictParametersBuilder::default()
+                    .predict_type(predict_type)
+                    .num_threads(1)
+                    .build()
+                    .unwrap();
+                assert_eq!(
+                    bst.predict_parallel_with_params(matrix, &params, num_workers)
+                        .unwrap(),
+                    bst.predict_with_params(matrix, &params).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn predict_file() {
+        let bst = _train_booster(&_default_params());
+        let test_file = "lightgbm-sys/lightgbm/examples/binary_classification/binary.test";
+        let rows = fs::read_to_string(test_file)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                line.split('\t')
+                    .skip(1)
+                    .map(|v| v.parse::<f64>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected = bst.predict_mat(rows.clone()).unwrap();
+
+        let result_file = "./test/test_predict_file.output";
+        bst.predict_file(test_file, false, result_file, &PredictParameters::default())
+            .unwrap();
+        let predicted = fs::read_to_string(result_file)
+            .unwrap()
+            .lines()
+            .map(|line| line.parse::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(predicted.len(), expected.num_rows());
+        assert!(predicted
+            .iter()
+            .zip(expected.as_slice())
+            .all(|(a, b)| (a - b).abs() < 1e-6));
+
+        let data_file = "./test/test_predict_file_header.input";
+        let header = (0..28)
+            .map(|i| format!("f{}", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        let body = rows[..3]
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(data_file, format!("{}\n{}\n", header, body)).unwrap();
+        let params = PredictParametersBuilder::default()
+            .predict_type(PredictType::LeafIndex)
+            .build()
+            .unwrap();
+        bst.predict_file(data_file, true, result_file, &params)
+            .unwrap();
+        assert_eq!(fs::read_to_string(result_file).unwrap().lines().count(), 3);
+
+        assert!(bst
+            .predict_file("./test/missing.input", false, result_file, &params)
+            .is_err());
+        let _ = fs::remove_file(data_file);
+        let _ = fs::remove_file(result_file);
+    }
+
+    #[test]
+    fn predict_with_params() {
+        let params = json! {
+            {
+                "num_iterations": 20,
+                "objective": "binary",
+                "data_random_seed": 0
+            }
+        };
+        let bst = _train_booster(&params);
+        let rows = (0..20)
+            .flat_map(|i| (0..28).map(move |j| ((i + j) % 5) as f64 / 5.0))
+            .collect::<Vec<f64>>();
+        let matrix = MatrixView::new(&rows, 20, 28, MatrixLayout::RowMajor).unwrap();
+        let full = bst
+            .predict_with_params(matrix, &PredictParameters::default())
+            .unwrap();
+        assert_eq!(full, bst.predict_slice(matrix).unwrap());
+
+        let first_trees = PredictParametersBuilder::default()
+            .predict_type(PredictType::RawScore)
+            .num_iteration(2)
+            .build()
+            .unwrap();
+        let early_stop = PredictParametersBuilder::default()
+            .predict_type(PredictType::RawScore)
+            .pred_early_stop(true)
+            .pred_early_stop_freq(2)
+            .pred_early_stop_margin(0.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            bst.predict_with_params(matrix, &early_stop).unwrap(),
+            bst.predict_with_params(matrix, &first_trees).unwrap()
+        );
+
+        let leaves = bst
+            .predict_with_params(matrix, &PredictType::LeafIndex.into())
+            .unwrap();
+        assert_eq!(leaves.num_rows(), 20);
+
+        let narrow = MatrixView::new(&rows[..20 * 14], 20, 14, MatrixLayout::RowMajor).unwrap();
+        assert!(bst
+            .predict_with_params(narrow, &PredictParameters::default())
+            .is_err());
+        let no_shape_check = PredictParametersBuilder::default()
+            .predict_disable_shape_check(true)
+            .build()
+            .unwrap();
+        assert!(bst.predict_with_params(narrow, &no_shape_check).is_ok());
+    }
+
     #[test]
     fn num_feature() {
         let params = _default_params();
@@ -276,10 +1527,30 @@ mod tests {
 
     #[test]
     fn feature_importance() {
-        let params = _default_params();
+        fn count_splits(node: &Value, splits: &mut [f64]) {
+            if let Some(feature) = node["split_feature"].as_u64() {
+                splits[feature as usize] += 1.0;
+                count_splits(&node["left_child"], splits);
+                count_splits(&node["right_child"], splits);
+            }
+        }
+
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "binary",
+                "data_random_seed": 0
+            }
+        };
         let bst = _train_booster(&params);
-        let feature_importance = bst.feature_importance().unwrap();
-        assert_eq!(feature_importance, vec![0.0; 28]);
+        let model = bst.dump_model().unwrap();
+        let trees = model["tree_info"].as_array().unwrap();
+        assert_eq!(trees.len(), 3);
+        let mut expected = vec![0.0; 28];
+        for tree in trees {
+            count_splits(&tree["tree_structure"], &mut expected);
+        }
+        assert_eq!(bst.feature_importance().unwrap(), expected);
     }
 
     #[test]
@@ -291,17 +1562,114 @@ mod tests {
         assert_eq!(feature_name, target);
     }
 
+    #[test]
+    fn feature_name_long_and_unicode() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+            vec![0.2, 0.2, 0.8],
+            vec![0.1, 0.7, 1.0],
+        ];
+        let label = vec![0.0, 0.0, 0.0, 1.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+        let long_name = "feature_with_a_name_longer_than_32_bytes".repeat(4);
+        let names = vec![long_name.as_str(), "年齢", "x"];
+        dataset.set_feature_names(&names).unwrap();
+        let bst = Booster::train(dataset, &_default_params()).unwrap();
+        assert_eq!(bst.feature_name().unwrap(), names);
+    }
+
     #[test]
     fn save_file() {
         let params = _default_params();
         let bst = _train_booster(&params);
-        assert_eq!(bst.save_file(&"./test/test_save_file.output"), Ok(()));
+        assert_eq!(bst.save_file("./test/test_save_file.output"), Ok(()));
         assert!(Path::new("./test/test_save_file.output").exists());
         let _ = fs::remove_file("./test/test_save_file.output");
     }
 
+    #[test]
+    fn dump_model() {
+        let params = json! {
+            {
+                "num_iterations": 3,
+                "objective": "binary",
+                "data_random_seed": 0
+            }
+        };
+        let bst = _train_booster(&params);
+        let model = bst.dump_model().unwrap();
+        assert_eq!(model["objective"], "binary sigmoid:1");
+        assert_eq!(model["max_feature_idx"], 27);
+        assert_eq!(model["tree_info"].as_array().unwrap().len(), 3);
+        assert!(model["tree_info"][0]["tree_structure"].is_object());
+    }
+
     #[test]
     fn from_file() {
-        let _ = Booster::from_file(&"./test/test_from_file.input");
+        let _ = Booster::from_file("./test/test_from_file.input");
+    }
+
+    fn _string_rows() -> (Vec<Vec<String>>, Vec<f32>) {
+        let colors = ["red", "green", "blue"];
+        (0..300)
+            .map(|i| {
+                let color = colors[i % 3];
+                let size = (i % 10) as f64 / 10.0;
+                let label = if color == "green" { 1.0 } else { 0.0 };
+                (vec![size.to_string(), color.to_string()], label)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn categorical_encoder_in_model_file() {
+        let filename = "./test/test_categorical_encoder_model.output";
+        let (rows, label) = _string_rows();
+        let encoder = CategoricalEncoder::fit(&rows, &[1]).unwrap();
+        let dataset = Dataset::from_mat_with_categorical_feature(
+            encoder.transform(&rows).unwrap(),
+            label,
+            &["size", "color"],
+            &[CategoricalFeature::from("color")],
+        )
+        .unwrap();
+        let params = json! {
+            {
+                "num_iterations": 5,
+                "objective": "binary",
+                "min_data_in_leaf": 1,
+                "min_data_per_group": 1,
+                "data_random_seed": 0
+            }
+        };
+        let bst = Booster::train(dataset, &params).unwrap();
+        assert!(bst.predict_strings(&rows).is_err());
+        bst.save_file(filename).unwrap();
+        encoder.save_to_model_file(filename).unwrap();
+
+        let loaded = Booster::from_file(filename).unwrap();
+        assert_eq!(loaded.categorical_encoder(), Some(&encoder));
+        let test_rows = vec![
+            vec!["0.5", "green"],
+            vec!["0.5", "red"],
+            vec!["0.5", "blue"],
+        ];
+        let result = loaded.predict_strings(&test_rows).unwrap();
+        assert_eq!(
+            result,
+            bst.predict_mat(encoder.transform(&test_rows).unwrap())
+                .unwrap()
+        );
+        let rows = result.rows().collect::<Vec<_>>();
+        assert!(rows[0][0] > 0.5);
+        assert!(rows[1][0] < 0.5);
+
+        // Saving the loaded booster keeps its encoder.
+        loaded.save_file(filename).unwrap();
+        let reloaded = Booster::from_file(filename).unwrap();
+        assert_eq!(reloaded.categorical_encoder(), Some(&encoder));
+        let _ = fs::remove_file(filename);
     }
 }
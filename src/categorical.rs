@@ -0,0 +1,371 @@
+//! Categorical features and the encoding of string categories to integer codes.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::json;
+
+use crate::utils::{append_model_line, read_model_line};
+use crate::{Column, Error, Result};
+
+/// Key of the model file line that holds a persisted [`CategoricalEncoder`].
+const MODEL_LINE_KEY: &str = "categorical_encoder:";
+
+/// A feature declared as categorical, referenced either by column index or by feature name.
+///
+/// Example
+/// ```
+/// use lightgbm::CategoricalFeature;
+///
+/// let by_index = CategoricalFeature::from(0);
+/// let by_name = CategoricalFeature::from("color");
+/// ```
+pub type CategoricalFeature = Column;
+
+/// Resolve categorical feature declarations to sorted, deduplicated column indices.
+///
+/// Names are looked up in `feature_names`, or in LightGBM's default names (`Column_0`, ...)
+/// when `feature_names` is empty.
+pub(crate) fn resolve_categorical_feature<S: AsRef<str>>(
+    categorical_feature: &[CategoricalFeature],
+    feature_names: &[S],
+    num_feature: usize,
+) -> Result<Vec<usize>> {
+    let mut indices = categorical_feature
+        .iter()
+        .map(|feature| match feature {
+            Column::Index(idx) if *idx < num_feature => Ok(*idx),
+            Column::Index(idx) => Err(Error::invalid_parameter(format!(
+                "categorical feature index {} is out of range for {} features",
+                idx, num_feature
+            ))),
+            Column::Name(name) => {
+                let position = if feature_names.is_empty() {
+                    (0..num_feature).position(|i| format!("Column_{}", i) == *name)
+                } else {
+                    feature_names.iter().position(|n| n.as_ref() == name)
+                };
+                position.ok_or_else(|| {
+                    Error::invalid_parameter(format!("unknown categorical feature '{}'", name))
+                })
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+/// How [`CategoricalEncoder::transform`] handles a category that was not seen during fitting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnseenCategory {
+    /// Encode the value as missing (`NaN`), which LightGBM routes like any other missing value.
+    Missing,
+    /// Fail the transformation.
+    Error,
+}
+
+/// Encode string categories to the non-negative integer codes required by LightGBM.
+///
+/// The encoder works on rows of string cells, as read from a CSV file for example. Categorical
+/// columns are mapped to codes in order of first appearance, all other columns are parsed as
+/// numbers (an empty cell is a missing value).
+///
+/// Attach the encoder to the trained model with
+/// [`Booster::with_categorical_encoder`](crate::Booster::with_categorical_encoder): the booster
+/// then predicts rows of string cells with [`Booster::predict_strings`](crate::Booster::predict_strings),
+/// and saves and restores the encoder with its model file, so prediction uses the same codes as
+/// training.
+///
+/// Example
+/// ```
+/// use lightgbm::{CategoricalEncoder, UnseenCategory};
+///
+/// let data = vec![vec!["red", "1.0"], vec!["blue", "0.5"], vec!["red", "0.2"]];
+/// let encoder = CategoricalEncoder::fit(&data, &[0]).unwrap();
+/// assert_eq!(
+///     encoder.transform(&data).unwrap(),
+///     vec![vec![0.0, 1.0], vec![1.0, 0.5], vec![0.0, 0.2]]
+/// );
+///
+/// let unseen = vec![vec!["green", "1.0"]];
+/// assert!(encoder.transform(&unseen).unwrap()[0][0].is_nan());
+/// let strict = encoder.with_unseen_category(UnseenCategory::Error);
+/// assert!(strict.transform(&unseen).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategoricalEncoder {
+    categorical_feature: Vec<usize>,
+    categories: Vec<Vec<String>>,
+    unseen_category: UnseenCategory,
+}
+
+impl CategoricalEncoder {
+    /// Learn the categories of the given columns from rows of string cells.
+    pub fn fit<S: AsRef<str>>(data: &[Vec<S>], categorical_feature: &[usize]) -> Result<Self> {
+        let mut categorical_feature = categorical_feature.to_vec();
+        categorical_feature.sort_unstable();
+        categorical_feature.dedup();
+
+        let mut categories = vec![Vec::new(); categorical_feature.len()];
+        let mut seen = vec![HashSet::new(); categorical_feature.len()];
+        for (row_idx, row) in data.iter().enumerate() {
+            for (i, &col) in categorical_feature.iter().enumerate() {
+                let value = row.get(col).map(|v| v.as_ref()).ok_or_else(|| {
+                    Error::invalid_input(format!("row {} has no column {}", row_idx, col))
+                })?;
+                if !value.is_empty() && seen[i].insert(value.to_string()) {
+                    categories[i].push(value.to_string());
+                }
+            }
+        }
+
+        Ok(Self {
+            categorical_feature,
+            categories,
+            unseen_category: UnseenCategory::Missing,
+        })
+    }
+
+    /// Set the policy for categories that were not seen during fitting.
+    pub fn with_unseen_category(mut self, unseen_category: UnseenCategory) -> Self {
+        self.unseen_category = unseen_category;
+        self
+    }
+
+    /// Indices of the encoded columns, to be declared as categorical features of the `Dataset`.
+    pub fn categorical_feature(&self) -> &[usize] {
+        &self.categorical_feature
+    }
+
+    /// Known categories of each encoded column; a category's code is its position.
+    pub fn categories(&self) -> &[Vec<String>] {
+        &self.categories
+    }
+
+    /// Convert rows of string cells to a numeric matrix, encoding the categorical columns.
+    pub fn transform<S: AsRef<str>>(&self, data: &[Vec<S>]) -> Result<Vec<Vec<f64>>> {
+        let codes = self.codes();
+        data.iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(col, value)| {
+                        let value = value.as_ref();
+                        match self.categorical_feature.binary_search(&col) {
+                            Ok(_) if value.is_empty() => Ok(f64::NAN),
+                            Ok(i) => self.encode(&codes[i], value, row_idx, col),
+                            Err(_) if value.is_empty() => Ok(f64::NAN),
+                            Err(_) => value.trim().parse::<f64>().map_err(|_| {
+                                Error::invalid_input(format!(
+                                    "cannot parse '{}' in row {}, column {} as a number",
+                                    value, row_idx, col
+                                ))
+                            }),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Create an encoder from the known categories of each categorical column.
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    pub(crate) fn from_categories(
+        categorical_feature: Vec<usize>,
+        categories: Vec<Vec<String>>,
+    ) -> Self {
+        Self {
+            categorical_feature,
+            categories,
+            unseen_category: UnseenCategory::Missing,
+        }
+    }
+
+    /// Whether column `col` is encoded as a categorical feature.
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    pub(crate) fn is_categorical(&self, col: usize) -> bool {
+        self.categorical_feature.binary_search(&col).is_ok()
+    }
+
+    /// Encode the values of the categorical column `col`, with `None` as a missing value.
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    pub(crate) fn encode_column<'a, I>(&self, col: usize, values: I) -> Result<Vec<f64>>
+    where
+        I: IntoIterator<Item = Option<&'a str>>,
+    {
+        let i = self
+            .categorical_feature
+            .binary_search(&col)
+            .map_err(|_| Error::invalid_input(format!("column {} is not categorical", col)))?;
+        let codes = self.codes().swap_remove(i);
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(row_idx, value)| match value {
+                Some(value) => self.encode(&codes, value, row_idx, col),
+                None => Ok(f64::NAN),
+            })
+            .collect()
+    }
+
+    /// Code of every known category, per categorical column.
+    fn codes(&self) -> Vec<HashMap<&str, f64>> {
+        self.categories
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .enumerate()
+                    .map(|(code, value)| (value.as_str(), code as f64))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn encode(
+        &self,
+        codes: &HashMap<&str, f64>,
+        value: &str,
+        row_idx: usize,
+        col: usize,
+    ) -> Result<f64> {
+        match (codes.get(value), self.unseen_category) {
+            (Some(code), _) => Ok(*code),
+            (None, UnseenCategory::Missing) => Ok(f64::NAN),
+            (None, UnseenCategory::Error) => Err(Error::invalid_input(format!(
+                "unseen category '{}' in row {}, column {}",
+                value, row_idx, col
+            ))),
+        }
+    }
+
+    /// Append the mapping to a model file written by [`Booster::save_file`](crate::Booster::save_file).
+    ///
+    /// LightGBM ignores the extra line when loading the model, and
+    /// [`Booster::from_file`](crate::Booster::from_file) restores the encoder from it.
+    pub fn save_to_model_file(&self, filename: &str) -> Result<()> {
+        let value = json!({
+            "categorical_feature": self.categorical_feature,
+            "categories": self.categories,
+            "unseen_category": match self.unseen_category {
+                UnseenCategory::Missing => "missing",
+                UnseenCategory::Error => "error",
+            },
+        });
+        append_model_line(filename, MODEL_LINE_KEY, &value)
+    }
+
+    /// Restore a mapping saved with [`CategoricalEncoder::save_to_model_file`].
+    pub fn from_model_file(filename: &str) -> Result<Self> {
+        Self::read_model_file(filename)?
+            .ok_or_else(|| Error::invalid_input(format!("no categorical encoder in {}", filename)))
+    }
+
+    /// Restore the mapping of a model file, if one was saved with it.
+    pub(crate) fn read_model_file(filename: &str) -> Result<Option<Self>> {
+        let value = match read_model_line(filename, MODEL_LINE_KEY)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let invalid = || Error::invalid_input("invalid categorical encoder");
+
+        let categorical_feature = value["categorical_feature"]
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|v| v.as_u64().map(|i| i as usize).ok_or_else(invalid))
+            .collect::<Result<Vec<_>>>()?;
+        let categories = value["categories"]
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|c| {
+                c.as_array()
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|v| v.as_str().map(String::from).ok_or_else(invalid))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let unseen_category = match value["unseen_category"].as_str() {
+            Some("error") => UnseenCategory::Error,
+            Some("missing") => UnseenCategory::Missing,
+            _ => return Err(invalid()),
+        };
+        if categorical_feature.len() != categories.len() {
+            return Err(invalid());
+        }
+
+        Ok(Some(Self {
+            categorical_feature,
+            categories,
+            unseen_category,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    fn _data() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["0.5", "red", "a"],
+            vec!["", "blue", "b"],
+            vec!["0.1", "red", ""],
+        ]
+    }
+
+    #[test]
+    fn resolve() {
+        let features = vec![CategoricalFeature::from("c"), CategoricalFeature::from(0)];
+        assert_eq!(
+            resolve_categorical_feature(&features, &["a", "b", "c"], 3),
+            Ok(vec![0, 2])
+        );
+        let features = vec![CategoricalFeature::from("Column_1")];
+        let no_names: &[&str] = &[];
+        assert_eq!(
+            resolve_categorical_feature(&features, no_names, 3),
+            Ok(vec![1])
+        );
+        assert!(resolve_categorical_feature(&[CategoricalFeature::from(3)], no_names, 3).is_err());
+        assert!(
+            resolve_categorical_feature(&[CategoricalFeature::from("d")], no_names, 3).is_err()
+        );
+    }
+
+    #[test]
+    fn fit_transform() {
+        let encoder = CategoricalEncoder::fit(&_data(), &[2, 1]).unwrap();
+        assert_eq!(encoder.categorical_feature(), &[1, 2]);
+        assert_eq!(encoder.categories(), &[vec!["red", "blue"], vec!["a", "b"]]);
+
+        let encoded = encoder.transform(&_data()).unwrap();
+        assert_eq!(encoded[0], vec![0.5, 0.0, 0.0]);
+        assert!(encoded[1][0].is_nan());
+        assert_eq!(&encoded[1][1..], &[1.0, 1.0]);
+        assert!(encoded[2][2].is_nan());
+
+        let unseen = vec![vec!["0.5", "green", "a"]];
+        assert!(encoder.transform(&unseen).unwrap()[0][1].is_nan());
+        let encoder = encoder.with_unseen_category(UnseenCategory::Error);
+        assert!(encoder.transform(&unseen).is_err());
+        assert!(encoder.transform(&[vec!["x", "red", "a"]]).is_err());
+    }
+
+    #[test]
+    fn save_and_load_model_file() {
+        let filename = "./test/test_categorical_encoder.output";
+        fs::write(filename, "tree\nend of parameters\n").unwrap();
+        let encoder = CategoricalEncoder::fit(&_data(), &[1, 2])
+            .unwrap()
+            .with_unseen_category(UnseenCategory::Error);
+        encoder.save_to_model_file(filename).unwrap();
+        assert_eq!(CategoricalEncoder::from_model_file(filename), Ok(encoder));
+        assert!(Path::new(filename).exists());
+        let _ = fs::remove_file(filename);
+    }
+}
@@ -0,0 +1,393 @@
+//! K-fold cross-validation.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::utils::SplitMix64;
+use crate::{Booster, Dataset, Error, Result};
+
+/// How rows are assigned to the folds of [`cv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FoldStrategy {
+    /// Rows are split into folds of equal size.
+    Plain,
+    /// Every fold gets the same share of each label value, for classification.
+    Stratified,
+    /// Query groups are kept whole within a fold, for ranking.
+    Group,
+}
+
+/// Parameters of [`cv`].
+///
+/// Example
+/// ```
+/// use lightgbm::{CvParametersBuilder, FoldStrategy};
+///
+/// let cv_params = CvParametersBuilder::default()
+///     .nfold(3)
+///     .strategy(FoldStrategy::Stratified)
+///     .early_stopping_rounds(10)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct CvParameters {
+    /// Number of folds.
+    #[builder(default = "5")]
+    nfold: usize,
+    /// How rows are assigned to folds.
+    #[builder(default = "FoldStrategy::Plain")]
+    strategy: FoldStrategy,
+    /// Whether to shuffle rows, or query groups, before assigning them to folds.
+    #[builder(default = "true")]
+    shuffle: bool,
+    /// Seed of the shuffling.
+    #[builder(default = "0")]
+    seed: u64,
+    /// Stop when the first metric, averaged across folds, has not improved for this many
+    /// iterations.
+    #[builder(default = "None", setter(into))]
+    early_stopping_rounds: Option<usize>,
+    /// Whether to keep the booster trained on each fold.
+    #[builder(default = "false")]
+    return_boosters: bool,
+}
+
+impl Default for CvParameters {
+    fn default() -> Self {
+        CvParametersBuilder::default().build().unwrap()
+    }
+}
+
+/// Metrics of every iteration of [`cv`], aggregated across folds.
+pub struct CvResult {
+    eval_names: Vec<String>,
+    mean: Vec<Vec<f64>>,
+    stdv: Vec<Vec<f64>>,
+    best_iteration: usize,
+    boosters: Vec<Booster>,
+}
+
+impl CvResult {
+    /// Names of the metrics, in the order of the values of an iteration.
+    pub fn eval_names(&self) -> &[String] {
+        &self.eval_names
+    }
+
+    /// Mean of the metrics on the validation folds, for every iteration up to the best one.
+    pub fn mean(&self) -> &[Vec<f64>] {
+        &self.mean
+    }
+
+    /// Standard deviation of the metrics on the validation folds, for every iteration up to
+    /// the best one.
+    pub fn stdv(&self) -> &[Vec<f64>] {
+        &self.stdv
+    }
+
+    /// Number of iterations with the best first metric when early stopping, or number of
+    /// iterations run otherwise.
+    pub fn best_iteration(&self) -> usize {
+        self.best_iteration
+    }
+
+    /// Booster trained on each fold, empty unless `return_boosters` is set.
+    ///
+    /// With early stopping, the boosters also hold the iterations run after the best one.
+    pub fn boosters(&self) -> &[Booster] {
+        &self.boosters
+    }
+
+    /// Take the booster trained on each fold.
+    pub fn into_boosters(self) -> Vec<Booster> {
+        self.boosters
+    }
+}
+
+/// Whether greater values of the metric are better, following LightGBM's naming of metrics.
+///
+/// `eval_name` is a name returned by [`Booster::eval_names`], e.g. `auc` or `ndcg@5`.
+pub fn is_higher_better(eval_name: &str) -> bool {
+    ["auc", "ndcg@", "map@", "average_precision"]
+        .iter()
+        .any(|prefix| eval_name.starts_with(prefix))
+}
+
+/// Split the rows of `dataset` into the `(train, valid)` row indices of every fold.
+fn make_folds(dataset: &Dataset, cv_params: &CvParameters) -> Result<Vec<(Vec<i32>, Vec<i32>)>> {
+    let num_data = dataset.num_data()? as usize;
+    let nfold = cv_params.nfold;
+    let mut rng = SplitMix64::new(cv_params.seed);
+    let mut shuffle = |items: &mut [usize]| {
+        if cv_params.shuffle {
+            rng.shuffle(items);
+        }
+    };
+
+    // rows, or query groups given as ranges of rows, in the order they are dealt to folds
+    let (units, ranges): (Vec<usize>, Option<Vec<i32>>) = match cv_params.strategy {
+        FoldStrategy::Plain => {
+            let mut rows = (0..num_data).collect::<Vec<_>>();
+            shuffle(&mut rows);
+            (rows, None)
+        }
+        FoldStrategy::Stratified => {
+            let mut by_label = BTreeMap::new();
+            for (row_idx, label) in dataset.label()?.iter().enumerate() {
+                by_label
+                    .entry(label.to_bits())
+                    .or_insert_with(Vec::new)
+                    .push(row_idx);
+            }
+            let mut rows = Vec::with_capacity(num_data);
+            for class_rows in by_label.values_mut() {
+                shuffle(class_rows);
+                rows.extend_from_slice(class_rows);
+            }
+            (rows, None)
+        }
+        FoldStrategy::Group => {
+            let boundaries = dataset.query_boundaries()?.ok_or_else(|| {
+                Error::invalid_input("group-aware folds need the query groups of the dataset")
+            })?;
+            let mut queries = (0..boundaries.len() - 1).collect::<Vec<_>>();
+            shuffle(&mut queries);
+            (queries, Some(boundaries))
+        }
+    };
+    if nfold < 2 || units.len() < nfold {
+        return Err(Error::invalid_parameter(format!(
+            "cannot split {} rows or query groups into {} folds",
+            units.len(),
+            nfold
+        )));
+    }
+
+    let mut fold_of_row = vec![0; num_data];
+    for (position, &unit) in units.iter().enumerate() {
+        let fold = match cv_params.strategy {
+            // deal rows of every label in turn, so each fold gets its share of every label
+            FoldStrategy::Stratified => position % nfold,
+            FoldStrategy::Plain | FoldStrategy::Group => position * nfold / units.len(),
+        };
+        match ranges {
+            Some(ref boundaries) => {
+                let rows = boundaries[unit] as usize..boundaries[unit + 1] as usize;
+                fold_of_row[rows].iter_mut().for_each(|f| *f = fold);
+            }
+            None => fold_of_row[unit] = fold,
+        }
+    }
+
+    Ok((0..nfold)
+        .map(|fold| {
+            let (valid, train) =
+                (0..num_data as i32).partition(|&row| fold_of_row[row as usize] == fold);
+            (train, valid)
+        })
+        .collect())
+}
+
+fn mean_and_stdv(scores: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+    let num_metric = scores.first().map_or(0, |s| s.len());
+    let n = scores.len() as f64;
+    (0..num_metric)
+        .map(|metric| {
+            let mean = scores.iter().map(|s| s[metric]).sum::<f64>() / n;
+            let variance = scores
+                .iter()
+                .map(|s| (s[metric] - mean).powi(2))
+                .sum::<f64>()
+                / n;
+            (mean, variance.sqrt())
+        })
+        .unzip()
+}
+
+/// Cross-validate the training parameters with k folds of `dataset`, like Python's
+/// `lightgbm.cv`.
+///
+/// A booster is trained on every fold, sharing the bins of `dataset`, and evaluated with the
+/// metrics of `parameter` on the held-out rows after every iteration. With early stopping,
+/// training stops once the first metric, averaged across folds, has not improved for
+/// `early_stopping_rounds` iterations, and the results end at the best iteration.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{cv, CvParametersBuilder, Dataset, FoldStrategy};
+/// use serde_json::json;
+///
+/// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+/// let params = json!{{"num_iterations": 20, "objective": "binary", "metric": "auc"}};
+/// let cv_params = CvParametersBuilder::default()
+///     .nfold(3)
+///     .strategy(FoldStrategy::Stratified)
+///     .early_stopping_rounds(5)
+///     .build()
+///     .unwrap();
+/// let result = cv(&dataset, &params, &cv_params).unwrap();
+/// let best_auc = result.mean()[result.best_iteration() - 1][0];
+/// ```
+pub fn cv(dataset: &Dataset, parameter: &Value, cv_params: &CvParameters) -> Result<CvResult> {
+    let mut boosters = make_folds(dataset, cv_params)?
+        .into_iter()
+        .map(|(train_idx, valid_idx)| {
+            let mut booster = Booster::create(dataset.subset(&train_idx)?, parameter)?;
+            booster.add_valid_data(dataset.subset(&valid_idx)?)?;
+            Ok(booster)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let eval_names = boosters[0].eval_names()?;
+    if cv_params.early_stopping_rounds.is_some() && eval_names.is_empty() {
+        return Err(Error::invalid_parameter(
+            "early stopping needs at least one metric",
+        ));
+    }
+    let higher_better = eval_names
+        .first()
+        .is_some_and(|name| is_higher_better(name));
+
+    let mut mean = Vec::new();
+    let mut stdv = Vec::new();
+    let mut best: Option<(usize, f64)> = None;
+    for iteration in 0..Booster::num_iterations(parameter)?.max(0) as usize {
+        let mut is_finished = true;
+        let mut scores = Vec::with_capacity(boosters.len());
+        for booster in &mut boosters {
+            is_finished &= booster.update_one_iter()?;
+            scores.push(booster.eval(1)?);
+        }
+        let (iteration_mean, iteration_stdv) = mean_and_stdv(&scores);
+        mean.push(iteration_mean);
+        stdv.push(iteration_stdv);
+
+        if let Some(rounds) = cv_params.early_stopping_rounds {
+            let score = mean[iteration][0];
+            match best {
+                Some((_, best_score))
+                    if (higher_better && score <= best_score)
+                        || (!higher_better && score >= best_score) => {}
+                _ => best = Some((iteration, score)),
+            }
+            if iteration - best.unwrap().0 >= rounds {
+                break;
+            }
+        }
+        if is_finished {
+            break;
+        }
+    }
+
+    let best_iteration = best.map_or(mean.len(), |(iteration, _)| iteration + 1);
+    mean.truncate(best_iteration);
+    stdv.truncate(best_iteration);
+    if !cv_params.return_boosters {
+        boosters.clear();
+    }
+    Ok(CvResult {
+        eval_names,
+        mean,
+        stdv,
+        best_iteration,
+        boosters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::tests::read_train_file;
+    use serde_json::json;
+
+    fn _cv_params(strategy: FoldStrategy) -> CvParameters {
+        CvParametersBuilder::default()
+            .nfold(3)
+            .strategy(strategy)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn folds() {
+        let data = (0..9).map(|i| vec![i as f64]).collect::<Vec<_>>();
+        let label = vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+
+        for strategy in &[FoldStrategy::Plain, FoldStrategy::Stratified] {
+            let folds = make_folds(&dataset, &_cv_params(*strategy)).unwrap();
+            assert_eq!(folds.len(), 3);
+            let mut valid_rows = folds
+                .iter()
+                .flat_map(|(_, valid)| valid.clone())
+                .collect::<Vec<_>>();
+            valid_rows.sort_unstable();
+            assert_eq!(valid_rows, (0..9).collect::<Vec<_>>());
+            assert!(folds
+                .iter()
+                .all(|(train, valid)| train.len() + valid.len() == 9));
+        }
+        let folds = make_folds(&dataset, &_cv_params(FoldStrategy::Stratified)).unwrap();
+        assert!(folds
+            .iter()
+            .all(|(_, valid)| valid.iter().filter(|&&row| row >= 6).count() == 1));
+
+        assert!(make_folds(&dataset, &_cv_params(FoldStrategy::Group)).is_err());
+        dataset.set_group(&[2, 3, 1, 3]).unwrap();
+        let folds = make_folds(&dataset, &_cv_params(FoldStrategy::Group)).unwrap();
+        for (_, valid) in &folds {
+            assert_eq!(valid.contains(&2), valid.contains(&4));
+            assert_eq!(valid.contains(&6), valid.contains(&8));
+        }
+
+        let too_many = CvParametersBuilder::default().nfold(10).build().unwrap();
+        assert!(make_folds(&dataset, &too_many).is_err());
+    }
+
+    #[test]
+    fn cross_validate() {
+        let params = json! {{"num_iterations": 5, "objective": "binary", "metric": "auc"}};
+        let result = cv(
+            &read_train_file().unwrap(),
+            &params,
+            &_cv_params(FoldStrategy::Plain),
+        )
+        .unwrap();
+        assert_eq!(result.eval_names(), &["auc"]);
+        assert_eq!(result.mean().len(), 5);
+        assert_eq!(result.stdv().len(), 5);
+        assert_eq!(result.best_iteration(), 5);
+        assert!(result.boosters().is_empty());
+        assert!(result.mean()[4][0] > 0.5);
+    }
+
+    #[test]
+    fn early_stopping() {
+        let params =
+            json! {{"num_iterations": 100, "objective": "binary", "metric": "binary_logloss"}};
+        let cv_params = CvParametersBuilder::default()
+            .nfold(3)
+            .strategy(FoldStrategy::Stratified)
+            .early_stopping_rounds(1)
+            .return_boosters(true)
+            .build()
+            .unwrap();
+        let result = cv(&read_train_file().unwrap(), &params, &cv_params).unwrap();
+        assert_eq!(result.mean().len(), result.best_iteration());
+        assert!(result.best_iteration() <= 100);
+        assert_eq!(result.into_boosters().len(), 3);
+
+        let params = json! {{"num_iterations": 5, "objective": "binary", "metric": "None"}};
+        assert!(cv(&read_train_file().unwrap(), &params, &cv_params).is_err());
+    }
+
+    #[test]
+    fn higher_better() {
+        assert!(is_higher_better("auc"));
+        assert!(is_higher_better("ndcg@5"));
+        assert!(!is_higher_better("binary_logloss"));
+        assert!(!is_higher_better("l2"));
+    }
+}
@@ -0,0 +1,238 @@
+//! Conversion of polars DataFrames to the dense matrices passed to LightGBM.
+
+use std::collections::HashSet;
+
+use polars::prelude::*;
+
+use crate::utils::group_sizes_from_ids;
+use crate::{CategoricalEncoder, Error, Result};
+
+fn series<'a>(dataframe: &'a DataFrame, name: &str) -> Result<&'a Series> {
+    Ok(dataframe.column(name)?.as_materialized_series())
+}
+
+fn is_number(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric() || dtype.is_bool()
+}
+
+fn is_category(dtype: &DataType) -> bool {
+    dtype.is_categorical() || dtype.is_enum() || dtype.is_string()
+}
+
+/// Learn the categories of the categorical, enum and string columns among `columns`, in order
+/// of first appearance.
+///
+/// Categories are kept by value, as the physical codes of a categorical column depend on the
+/// frame it was built in.
+pub(crate) fn fit_categories<S: AsRef<str>>(
+    dataframe: &DataFrame,
+    columns: &[S],
+) -> Result<CategoricalEncoder> {
+    let mut categorical_feature = Vec::new();
+    let mut categories = Vec::new();
+    for (col_idx, name) in columns.iter().enumerate() {
+        let series = series(dataframe, name.as_ref())?;
+        if is_category(series.dtype()) {
+            let values = series.cast(&DataType::String)?;
+            let mut seen = HashSet::new();
+            categories.push(
+                values
+                    .str()?
+                    .iter()
+                    .flatten()
+                    .filter(|value| seen.insert(*value))
+                    .map(String::from)
+                    .collect(),
+            );
+            categorical_feature.push(col_idx);
+        }
+    }
+    Ok(CategoricalEncoder::from_categories(
+        categorical_feature,
+        categories,
+    ))
+}
+
+/// Copy the given columns into a column-major matrix.
+///
+/// Numeric and boolean columns are converted to `f64` with nulls as `NaN`. Categorical, enum and
+/// string columns are encoded by value with `encoder`, and must be its categorical features.
+pub(crate) fn feature_matrix<S: AsRef<str>>(
+    dataframe: &DataFrame,
+    columns: &[S],
+    encoder: Option<&CategoricalEncoder>,
+) -> Result<Vec<f64>> {
+    let mut data = Vec::with_capacity(dataframe.height() * columns.len());
+    for (col_idx, name) in columns.iter().enumerate() {
+        let name = name.as_ref();
+        let series = series(dataframe, name)?;
+        let dtype = series.dtype();
+        let encoder = encoder.filter(|encoder| encoder.is_categorical(col_idx));
+        if is_category(dtype) {
+            let encoder = encoder.ok_or_else(|| {
+                Error::invalid_input(format!(
+                    "column '{}' of type {} is not a known categorical feature",
+                    name, dtype
+                ))
+            })?;
+            let values = series.cast(&DataType::String)?;
+            data.extend(encoder.encode_column(col_idx, values.str()?.iter())?);
+        } else if encoder.is_some() {
+            return Err(Error::invalid_input(format!(
+                "categorical feature '{}' has type {} instead of categories",
+                name, dtype
+            )));
+        } else if is_number(dtype) {
+            data.extend(
+                series
+                    .cast(&DataType::Float64)?
+                    .f64()?
+                    .iter()
+                    .map(|v| v.unwrap_or(f64::NAN)),
+            );
+        } else {
+            return Err(Error::invalid_input(format!(
+                "column '{}' of type {} is neither numeric nor categorical",
+                name, dtype
+            )));
+        }
+    }
+    Ok(data)
+}
+
+/// Read a numeric column without nulls, such as the label or the weights, as `f32`.
+pub(crate) fn f32_column(dataframe: &DataFrame, name: &str) -> Result<Vec<f32>> {
+    let series = series(dataframe, name)?;
+    if !is_number(series.dtype()) {
+        return Err(Error::invalid_input(format!(
+            "column '{}' of type {} is not numeric",
+            name,
+            series.dtype()
+        )));
+    }
+    series
+        .cast(&DataType::Float32)?
+        .f32()?
+        .iter()
+        .enumerate()
+        .map(|(row_idx, v)| {
+            v.ok_or_else(|| {
+                Error::invalid_input(format!("null in row {} of column '{}'", row_idx, name))
+            })
+        })
+        .collect()
+}
+
+/// Read an integer column of query ids as the sizes of consecutive groups.
+pub(crate) fn group_sizes(dataframe: &DataFrame, name: &str) -> Result<Vec<i32>> {
+    let series = series(dataframe, name)?;
+    if !series.dtype().is_integer() {
+        return Err(Error::invalid_input(format!(
+            "group column '{}' of type {} is not an integer column",
+            name,
+            series.dtype()
+        )));
+    }
+    group_sizes_from_ids(series.cast(&DataType::Int64)?.i64()?.iter(), name)
+}
+
+/// Wrap predictions, flat in row-major order, in a DataFrame with one column per output value.
+///
+/// A single output is named `prediction`, several outputs `prediction_0`, `prediction_1`, ...
+pub(crate) fn prediction_frame(predictions: &[f64], num_rows: usize) -> Result<DataFrame> {
+    let num_cols = predictions.len().checked_div(num_rows).unwrap_or(0);
+    let columns = (0..num_cols)
+        .map(|col| {
+            let name = if num_cols == 1 {
+                String::from("prediction")
+            } else {
+                format!("prediction_{}", col)
+            };
+            let values = predictions
+                .iter()
+                .skip(col)
+                .step_by(num_cols)
+                .copied()
+                .collect::<Vec<f64>>();
+            Column::new(name.into(), values)
+        })
+        .collect();
+    Ok(DataFrame::new(columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::df;
+
+    fn _dataframe() -> DataFrame {
+        df![
+            "x" => [Some(0.5), None, Some(1.0), Some(2.0)],
+            "color" => [Some("red"), None, Some("blue"), Some("red")],
+            "query" => [7, 7, 3, 3],
+            "flag" => [Some(true), Some(false), None, Some(true)],
+        ]
+        .unwrap()
+    }
+
+    #[test]
+    fn features() {
+        let encoder = fit_categories(&_dataframe(), &["color", "x"]).unwrap();
+        assert_eq!(encoder.categorical_feature(), &[0]);
+        assert_eq!(encoder.categories(), &[vec!["red", "blue"]]);
+        let data = feature_matrix(&_dataframe(), &["color", "x"], Some(&encoder)).unwrap();
+        assert_eq!(&data[..1], &[0.0]);
+        assert!(data[1].is_nan());
+        assert_eq!(&data[2..4], &[1.0, 0.0]);
+        assert_eq!(data[4], 0.5);
+        assert!(data[5].is_nan());
+        assert_eq!(&data[6..], &[1.0, 2.0]);
+
+        assert!(feature_matrix(&_dataframe(), &["y"], None).is_err());
+        assert!(feature_matrix(&_dataframe(), &["color"], None).is_err());
+        assert!(feature_matrix(&_dataframe(), &["x"], Some(&encoder)).is_err());
+    }
+
+    #[test]
+    fn categories_in_another_order() {
+        let encoder = fit_categories(&_dataframe(), &["color"]).unwrap();
+        let color = Series::new(
+            "color".into(),
+            [Some("blue"), Some("green"), Some("red"), None],
+        )
+        .cast(&DataType::Categorical(None, CategoricalOrdering::Physical))
+        .unwrap();
+        let reordered = DataFrame::new(vec![color.into()]).unwrap();
+        assert!(reordered.column("color").unwrap().dtype().is_categorical());
+        let data = feature_matrix(&reordered, &["color"], Some(&encoder)).unwrap();
+        assert_eq!(data[0], 1.0);
+        assert!(data[1].is_nan());
+        assert_eq!(data[2], 0.0);
+        assert!(data[3].is_nan());
+    }
+
+    #[test]
+    fn label_and_group() {
+        assert_eq!(
+            f32_column(&_dataframe(), "query"),
+            Ok(vec![7.0, 7.0, 3.0, 3.0])
+        );
+        assert!(f32_column(&_dataframe(), "flag").is_err());
+        assert!(f32_column(&_dataframe(), "color").is_err());
+        assert_eq!(group_sizes(&_dataframe(), "query"), Ok(vec![2, 2]));
+        assert!(group_sizes(&_dataframe(), "x").is_err());
+        assert!(group_sizes(&df!["query" => [1, 2, 1]].unwrap(), "query").is_err());
+    }
+
+    #[test]
+    fn predictions() {
+        let frame = prediction_frame(&[0.1, 0.9, 0.3, 0.7], 2).unwrap();
+        assert_eq!(frame.get_column_names(), &["prediction_0", "prediction_1"]);
+        assert_eq!(
+            frame.column("prediction_1").unwrap().f64().unwrap().get(1),
+            Some(0.7)
+        );
+        let frame = prediction_frame(&[0.1, 0.9], 2).unwrap();
+        assert_eq!(frame.get_column_names(), &["prediction"]);
+    }
+}
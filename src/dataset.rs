@@ -2,11 +2,31 @@ use libc::{c_char, c_void};
 use lightgbm_sys;
 use std;
 use std::ffi::CString;
+use std::fs::File;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(feature = "dataframe")]
-use polars::prelude::*;
+use polars::prelude::DataFrame;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayView1, ArrayView2};
+
+#[cfg(feature = "arrow")]
+use arrow::record_batch::RecordBatch;
 
-use crate::{Error, Result};
+#[cfg(feature = "dataframe")]
+use crate::dataframe;
+use crate::matrix::flatten_rows;
+#[cfg(feature = "ndarray")]
+use crate::matrix::with_array_view;
+#[cfg(feature = "arrow")]
+use crate::record_batch;
+use crate::utils::read_string_array;
+use crate::{
+    CategoricalEncoder, CategoricalFeature, DType, DatasetParameters, DatasetParametersBuilder,
+    DatasetStream, Error, MatrixLayout, MatrixView, Result,
+};
 
 /// Dataset used throughout LightGBM for training.
 ///
@@ -33,14 +53,40 @@ use crate::{Error, Result};
 ///
 /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
 /// ```
+///
+/// ## Thread safety
+///
+/// A `Dataset` is `Send` and `Sync`: methods taking `&self` only read it, so it can be shared
+/// between threads, for example to build validation sets or subsets concurrently. Methods
+/// changing it, such as [`Dataset::set_label`], take `&mut self`.
 pub struct Dataset {
     pub(crate) handle: lightgbm_sys::DatasetHandle,
+    /// Identifies the bin mappers of the dataset, shared by datasets created with a reference.
+    pub(crate) bin_mapper_id: usize,
+    /// Categories of the columns of a DataFrame or record batch, shared by datasets created with a reference and
+    /// by the boosters trained on it.
+    pub(crate) categorical_encoder: Option<CategoricalEncoder>,
 }
 
-#[link(name = "c")]
+/// Leading bytes of a LightGBM binary dataset file.
+const BINARY_FILE_TOKEN: &[u8] = b"______LightGBM_Binary_File_Token______\n";
+
+/// Number of rows pushed at once by [`Dataset::from_row_iter`].
+const STREAM_CHUNK_ROWS: usize = 4096;
+
+static NEXT_BIN_MAPPER_ID: AtomicUsize = AtomicUsize::new(0);
+
 impl Dataset {
-    fn new(handle: lightgbm_sys::DatasetHandle) -> Self {
-        Self { handle }
+    pub(crate) fn new(handle: lightgbm_sys::DatasetHandle, reference: Option<&Dataset>) -> Self {
+        let bin_mapper_id = match reference {
+            Some(reference) => reference.bin_mapper_id,
+            None => NEXT_BIN_MAPPER_ID.fetch_add(1, Ordering::Relaxed),
+        };
+        Self {
+            handle,
+            bin_mapper_id,
+            categorical_encoder: reference.and_then(|r| r.categorical_encoder.clone()),
+        }
     }
 
     /// Create a new `Dataset` from dense array in row-major order.
@@ -58,34 +104,309 @@ impl Dataset {
     /// let dataset = Dataset::from_mat(data, label).unwrap();
     /// ```
     pub fn from_mat(data: Vec<Vec<f64>>, label: Vec<f32>) -> Result<Self> {
-        let data_length = data.len();
-        let feature_length = data[0].len();
-        let params = CString::new("").unwrap();
-        let label_str = CString::new("label").unwrap();
-        let reference = std::ptr::null_mut(); // not use
+        Self::from_mat_with_params(data, label, &DatasetParameters::default(), None)
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order, sharing the bin mappers of
+    /// `reference`.
+    ///
+    /// Use this for validation data, so that it is binned exactly like the training data.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1, 0.2],
+    ///                vec![0.7, 0.4, 0.5],
+    ///                vec![0.1, 0.7, 1.0]];
+    /// let label = vec![0.0, 0.0, 1.0];
+    /// let train = Dataset::from_mat(data, label).unwrap();
+    ///
+    /// let valid_data = vec![vec![0.9, 0.2, 0.3],
+    ///                      vec![0.2, 0.8, 0.9]];
+    /// let valid_label = vec![0.0, 1.0];
+    /// let valid = Dataset::from_mat_with_reference(valid_data, valid_label, &train).unwrap();
+    /// ```
+    pub fn from_mat_with_reference(
+        data: Vec<Vec<f64>>,
+        label: Vec<f32>,
+        reference: &Dataset,
+    ) -> Result<Self> {
+        Self::from_mat_with_params(data, label, &DatasetParameters::default(), Some(reference))
+    }
+
+    /// Create a new `Dataset` from a dense matrix borrowed from a contiguous `f32` or `f64`
+    /// slice, without copying it.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, MatrixLayout, MatrixView};
+    ///
+    /// let data = vec![1.0_f32, 0.1, 0.2,
+    ///                 0.7, 0.4, 0.5,
+    ///                 0.1, 0.7, 1.0];
+    /// let label = vec![0.0, 0.0, 1.0];
+    /// let matrix = MatrixView::new(&data, 3, 3, MatrixLayout::RowMajor).unwrap();
+    /// let dataset = Dataset::from_slice(matrix, &label).unwrap();
+    /// ```
+    pub fn from_slice<T: DType>(data: MatrixView<T>, label: &[f32]) -> Result<Self> {
+        Self::from_slice_with_params(data, label, &DatasetParameters::default(), None)
+    }
+
+    /// Create a new `Dataset` from a borrowed dense matrix with construction parameters,
+    /// optionally sharing the bin mappers of `reference`.
+    ///
+    /// Values of categorical features must be non-negative integers or `NaN` (missing).
+    pub fn from_slice_with_params<T: DType>(
+        data: MatrixView<T>,
+        label: &[f32],
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let (parameter, categorical_idx) = params.to_mat_string(data.num_cols())?;
+        data.check_categorical(&categorical_idx)?;
+
+        let mut dataset = Self::create_from_matrix(data, label, &parameter, reference)?;
+        if !params.feature_names().is_empty() {
+            dataset.set_feature_names(params.feature_names())?;
+        }
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from an ndarray matrix and labels.
+    ///
+    /// The matrix is passed to LightGBM without copying when it is contiguous in C or Fortran
+    /// order.
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "ndarray",
+        doc = r##"
+```
+extern crate ndarray;
+
+use lightgbm::Dataset;
+use ndarray::{arr1, arr2};
+
+let data = arr2(&[[1.0, 0.1, 0.2],
+                  [0.7, 0.4, 0.5],
+                  [0.1, 0.7, 1.0]]);
+let label = arr1(&[0.0_f32, 0.0, 1.0]);
+let dataset = Dataset::from_array(data.view(), label.view()).unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "ndarray")]
+    pub fn from_array<T: DType>(data: ArrayView2<T>, label: ArrayView1<f32>) -> Result<Self> {
+        Self::from_array_with_params(data, label, &DatasetParameters::default(), None)
+    }
+
+    /// Create a new `Dataset` from an ndarray matrix and labels with construction parameters,
+    /// optionally sharing the bin mappers of `reference`.
+    ///
+    /// Note: the feature ```ndarray``` is required for this method
+    #[cfg(feature = "ndarray")]
+    pub fn from_array_with_params<T: DType>(
+        data: ArrayView2<T>,
+        label: ArrayView1<f32>,
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let label = label.to_vec();
+        with_array_view(&data, |matrix| {
+            Self::from_slice_with_params(matrix, &label, params, reference)
+        })
+    }
+
+    fn create_from_matrix<T: DType>(
+        data: MatrixView<T>,
+        label: &[f32],
+        parameter: &str,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        if label.len() != data.num_rows() {
+            return Err(Error::invalid_input(format!(
+                "got {} labels for {} rows",
+                label.len(),
+                data.num_rows()
+            )));
+        }
+        let params = CString::new(parameter)?;
+        let label_str = CString::new("label")?;
         let mut handle = std::ptr::null_mut();
-        let flat_data = data.into_iter().flatten().collect::<Vec<_>>();
 
         lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromMat(
-            flat_data.as_ptr() as *const c_void,
-            lightgbm_sys::C_API_DTYPE_FLOAT64 as i32,
-            data_length as i32,
-            feature_length as i32,
-            1_i32,
+            data.as_slice().as_ptr() as *const c_void,
+            T::C_API_DTYPE,
+            data.num_rows() as i32,
+            data.num_cols() as i32,
+            data.is_row_major(),
             params.as_ptr() as *const c_char,
-            reference,
+            reference.map_or(std::ptr::null_mut(), |r| r.handle),
             &mut handle
         ))?;
+        let dataset = Self::new(handle, reference);
 
         lgbm_call!(lightgbm_sys::LGBM_DatasetSetField(
-            handle,
+            dataset.handle,
             label_str.as_ptr() as *const c_char,
             label.as_ptr() as *const c_void,
-            data_length as i32,
+            label.len() as i32,
             lightgbm_sys::C_API_DTYPE_FLOAT32 as i32
         ))?;
 
-        Ok(Self::new(handle))
+        Ok(dataset)
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order with explicit feature names.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1, 0.2],
+    ///                vec![0.7, 0.4, 0.5],
+    ///                vec![0.1, 0.7, 1.0]];
+    /// let label = vec![0.0, 0.0, 1.0];
+    /// let dataset = Dataset::from_mat_with_feature_names(data, label, &["age", "height", "weight"]).unwrap();
+    /// ```
+    pub fn from_mat_with_feature_names<S: AsRef<str>>(
+        data: Vec<Vec<f64>>,
+        label: Vec<f32>,
+        feature_names: &[S],
+    ) -> Result<Self> {
+        Self::from_mat_with_categorical_feature(data, label, feature_names, &[])
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order with categorical features.
+    ///
+    /// Categorical features are declared by index or by name. Names are looked up in
+    /// `feature_names`, which also become the feature names of the `Dataset`; pass an empty
+    /// slice to keep LightGBM's default names (`Column_0`, `Column_1`, ...).
+    /// Values of categorical features must be non-negative integers or `NaN` (missing),
+    /// see [`CategoricalEncoder`](crate::CategoricalEncoder) to encode string categories.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{CategoricalFeature, Dataset};
+    ///
+    /// let data = vec![vec![1.0, 0.1, 2.0],
+    ///                vec![0.7, 0.4, 0.0],
+    ///                vec![0.1, 0.7, 1.0]];
+    /// let label = vec![0.0, 0.0, 1.0];
+    /// let dataset = Dataset::from_mat_with_categorical_feature(
+    ///     data,
+    ///     label,
+    ///     &["age", "height", "color"],
+    ///     &[CategoricalFeature::from("color")],
+    /// ).unwrap();
+    /// ```
+    pub fn from_mat_with_categorical_feature<S: AsRef<str>>(
+        data: Vec<Vec<f64>>,
+        label: Vec<f32>,
+        feature_names: &[S],
+        categorical_feature: &[CategoricalFeature],
+    ) -> Result<Self> {
+        let params = DatasetParametersBuilder::default()
+            .feature_names(
+                feature_names
+                    .iter()
+                    .map(|name| name.as_ref().to_string())
+                    .collect(),
+            )
+            .categorical_feature(categorical_feature.to_vec())
+            .build()
+            .map_err(Error::invalid_parameter)?;
+        Self::from_mat_with_params(data, label, &params, None)
+    }
+
+    /// Create a new `Dataset` from dense array in row-major order with construction parameters,
+    /// optionally sharing the bin mappers of `reference`.
+    ///
+    /// Values of categorical features must be non-negative integers or `NaN` (missing).
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, DatasetParametersBuilder};
+    ///
+    /// let data = vec![vec![1.0, 0.1, 0.2],
+    ///                vec![0.7, 0.4, 0.5],
+    ///                vec![0.1, 0.7, 1.0]];
+    /// let label = vec![0.0, 0.0, 1.0];
+    /// let params = DatasetParametersBuilder::default()
+    ///     .max_bin(15)
+    ///     .zero_as_missing(true)
+    ///     .build()
+    ///     .unwrap();
+    /// let dataset = Dataset::from_mat_with_params(data, label, &params, None).unwrap();
+    /// ```
+    pub fn from_mat_with_params(
+        data: Vec<Vec<f64>>,
+        label: Vec<f32>,
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let (flat_data, data_length, feature_length) = flatten_rows(data)?;
+        let matrix = MatrixView::new(
+            &flat_data,
+            data_length,
+            feature_length,
+            MatrixLayout::RowMajor,
+        )?;
+        Self::from_slice_with_params(matrix, &label, params, reference)
+    }
+
+    /// Create a new `Dataset` from an iterator of rows with their labels, without holding
+    /// every row in memory.
+    ///
+    /// The iterator must yield exactly `num_total_row` rows. Feature bins are built from the
+    /// first `bin_construct_sample_cnt` rows, which should therefore be representative of the
+    /// data, and the rows are then pushed in chunks through a [`DatasetStream`](crate::DatasetStream).
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Dataset, DatasetParameters};
+    /// use std::io::{BufRead, Cursor};
+    ///
+    /// let reader = Cursor::new("0,1.0,0.1\n0,0.7,0.4\n1,0.2,0.8\n1,0.1,0.9\n");
+    /// let rows = reader.lines().map(|line| {
+    ///     let values: Vec<f64> = line.unwrap().split(',').map(|v| v.parse().unwrap()).collect();
+    ///     (values[1..].to_vec(), values[0] as f32)
+    /// });
+    /// let dataset = Dataset::from_row_iter(rows, 4, &DatasetParameters::default()).unwrap();
+    /// assert_eq!(dataset.num_data(), Ok(4));
+    /// ```
+    pub fn from_row_iter<I>(
+        rows: I,
+        num_total_row: usize,
+        params: &DatasetParameters,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Vec<f64>, f32)>,
+    {
+        let mut rows = rows.into_iter();
+        let mut next_chunk = |num_rows: usize| {
+            let (chunk, label): (Vec<_>, Vec<_>) = rows.by_ref().take(num_rows).unzip();
+            flatten_rows(chunk).map(|(data, num_rows, num_cols)| (data, num_rows, num_cols, label))
+        };
+
+        let (sample, num_rows, num_cols, label) =
+            next_chunk(num_total_row.min(params.bin_construct_sample_cnt()))?;
+        let sample = MatrixView::new(&sample, num_rows, num_cols, MatrixLayout::RowMajor)?;
+        let mut stream = DatasetStream::from_sample(sample, num_total_row, params)?;
+        stream.push_rows(sample, &label)?;
+        loop {
+            let (chunk, num_rows, num_cols, label) = next_chunk(STREAM_CHUNK_ROWS)?;
+            if num_rows == 0 {
+                break;
+            }
+            let chunk = MatrixView::new(&chunk, num_rows, num_cols, MatrixLayout::RowMajor)?;
+            stream.push_rows(chunk, &label)?;
+        }
+        stream.finish()
     }
 
     /// Create a new `Dataset` from file.
@@ -101,6 +422,9 @@ impl Dataset {
     /// 0 0.1 0.9 1.0
     /// ```
     ///
+    /// A LightGBM binary dataset file written by [`Dataset::save_binary`] is recognized and
+    /// loaded without binning again.
+    ///
     /// Example
     /// ```
     /// use lightgbm::Dataset;
@@ -108,21 +432,184 @@ impl Dataset {
     /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train");
     /// ```
     pub fn from_file(file_path: &str) -> Result<Self> {
-        let file_path_str = CString::new(file_path).unwrap();
-        let params = CString::new("").unwrap();
+        Self::create_from_file(file_path, "", None)
+    }
+
+    /// Load a LightGBM binary dataset file written by [`Dataset::save_binary`].
+    ///
+    /// Unlike [`Dataset::from_file`], this fails if the file is not a binary dataset file.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// dataset.save_binary(&"binary.train.bin").unwrap();
+    /// let binned = Dataset::from_binary_file(&"binary.train.bin").unwrap();
+    /// # std::fs::remove_file("binary.train.bin").unwrap();
+    /// ```
+    pub fn from_binary_file(file_path: &str) -> Result<Self> {
+        Self::check_binary_file(file_path)?;
+        Self::create_from_file(file_path, "", None)
+    }
+
+    /// Load a LightGBM binary dataset file of validation data, sharing the bin mappers of
+    /// `reference`.
+    ///
+    /// The file must have been saved from a `Dataset` created with the same reference data, as
+    /// it keeps the bin mappers it was binned with; LightGBM checks that they match when the
+    /// dataset is added to a [`Booster`](crate::Booster).
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let train = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// valid.save_binary(&"binary.test.bin").unwrap();
+    /// let binned = Dataset::from_binary_file_with_reference(&"binary.test.bin", &train).unwrap();
+    /// assert!(binned.shares_bin_mappers_with(&train));
+    /// # std::fs::remove_file("binary.test.bin").unwrap();
+    /// ```
+    pub fn from_binary_file_with_reference(file_path: &str, reference: &Dataset) -> Result<Self> {
+        Self::check_binary_file(file_path)?;
+        Self::create_from_file(file_path, "", Some(reference))
+    }
+
+    fn check_binary_file(file_path: &str) -> Result<()> {
+        let mut token = vec![0_u8; BINARY_FILE_TOKEN.len()];
+        File::open(file_path)
+            .and_then(|mut file| file.read_exact(&mut token))
+            .map_err(|e| Error::io(file_path, e))?;
+        if token != BINARY_FILE_TOKEN {
+            return Err(Error::invalid_input(format!(
+                "{} is not a LightGBM binary dataset file",
+                file_path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Create a new `Dataset` from file, sharing the bin mappers of `reference`.
+    ///
+    /// Use this for validation data, so that it is binned exactly like the training data.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let train = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let valid = Dataset::from_file_with_reference(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+    /// ```
+    pub fn from_file_with_reference(file_path: &str, reference: &Dataset) -> Result<Self> {
+        Self::from_file_with_params(file_path, &DatasetParameters::default(), Some(reference))
+    }
+
+    /// Create a new `Dataset` from file with construction parameters, optionally sharing the
+    /// bin mappers of `reference`.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::{Column, Dataset, DatasetParametersBuilder};
+    ///
+    /// let params = DatasetParametersBuilder::default()
+    ///     .max_bin(63)
+    ///     .label_column(Column::from(0))
+    ///     .build()
+    ///     .unwrap();
+    /// let dataset = Dataset::from_file_with_params(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train", &params, None).unwrap();
+    /// ```
+    pub fn from_file_with_params(
+        file_path: &str,
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let parameter = params.to_file_string()?;
+        let mut dataset = Self::create_from_file(file_path, &parameter, reference)?;
+        if !params.feature_names().is_empty() {
+            dataset.set_feature_names(params.feature_names())?;
+        }
+        Ok(dataset)
+    }
+
+    fn create_from_file(
+        file_path: &str,
+        parameter: &str,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let file_path_str = CString::new(file_path)?;
+        let params = CString::new(parameter)?;
         let mut handle = std::ptr::null_mut();
 
         lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromFile(
             file_path_str.as_ptr() as *const c_char,
             params.as_ptr() as *const c_char,
-            std::ptr::null_mut(),
+            reference.map_or(std::ptr::null_mut(), |r| r.handle),
             &mut handle
         ))?;
 
-        Ok(Self::new(handle))
+        Ok(Self::new(handle, reference))
+    }
+
+    /// Whether this `Dataset` shares its bin mappers with `other`.
+    pub fn shares_bin_mappers_with(&self, other: &Dataset) -> bool {
+        self.bin_mapper_id == other.bin_mapper_id
+    }
+
+    /// Create a new `Dataset` with the rows at `used_row_indices`, without binning again.
+    ///
+    /// The subset shares the bin mappers of this `Dataset` and keeps the labels, weights and
+    /// query groups of the selected rows. Indices must be strictly increasing, and the rows of
+    /// a query group must be selected together.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let dataset = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+    /// let fold = (0..7000).filter(|i| i % 5 != 0).collect::<Vec<i32>>();
+    /// let train = dataset.subset(&fold).unwrap();
+    /// assert_eq!(train.num_data(), Ok(5600));
+    /// ```
+    pub fn subset(&self, used_row_indices: &[i32]) -> Result<Self> {
+        let num_data = self.num_data()?;
+        if used_row_indices.is_empty() {
+            return Err(Error::invalid_input("a subset needs at least one row"));
+        }
+        if used_row_indices.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Error::invalid_input(
+                "row indices must be strictly increasing",
+            ));
+        }
+        if let Some(idx) = used_row_indices
+            .iter()
+            .find(|&&idx| idx < 0 || idx >= num_data)
+        {
+            return Err(Error::invalid_input(format!(
+                "row index {} is out of range for {} rows",
+                idx, num_data
+            )));
+        }
+
+        let params = CString::new("")?;
+        let mut handle = std::ptr::null_mut();
+        lgbm_call!(lightgbm_sys::LGBM_DatasetGetSubset(
+            self.handle,
+            used_row_indices.as_ptr(),
+            used_row_indices.len() as i32,
+            params.as_ptr() as *const c_char,
+            &mut handle
+        ))?;
+        Ok(Self::new(handle, Some(self)))
     }
 
-    /// Create a new `Dataset` from a polars DataFrame.
+    /// Create a new `Dataset` from a polars DataFrame, using every other column as a feature.
+    ///
+    /// Numeric and boolean columns are features with nulls as missing values. Categorical, enum
+    /// and string columns are categorical features: their categories are recorded in a
+    /// [`CategoricalEncoder`], in order of first appearance, and encoded by value. Boosters trained
+    /// on the dataset keep the encoder, so prediction uses the same codes whatever the category
+    /// order of its frame. Feature names are the column names.
     ///
     /// Note: the feature ```dataframe``` is required for this method
     ///
@@ -131,79 +618,406 @@ impl Dataset {
     #[cfg_attr(
         feature = "dataframe",
         doc = r##"
-    extern crate polars;
+```
+extern crate polars;
 
-    use lightgbm::Dataset;
-    use polars::prelude::*;
-    use polars::df;
+use lightgbm::Dataset;
+use polars::prelude::*;
+use polars::df;
 
-    let df: DataFrame = df![
-            "feature_1" => [1.0, 0.7, 0.9, 0.2, 0.1],
-            "feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
-            "feature_3" => [0.2, 0.5, 0.5, 0.1, 0.1],
-            "feature_4" => [0.1, 0.1, 0.1, 0.7, 0.9],
-            "label" => [0.0, 0.0, 0.0, 1.0, 1.0]
-        ].unwrap();
-    let dataset = Dataset::from_dataframe(df, String::from("label")).unwrap();
-    "##
+let df: DataFrame = df![
+        "feature_1" => [Some(1.0), Some(0.7), None, Some(0.2), Some(0.1)],
+        "feature_2" => [0.1, 0.4, 0.8, 0.2, 0.7],
+        "color" => ["red", "red", "blue", "green", "blue"],
+        "label" => [0.0, 0.0, 0.0, 1.0, 1.0]
+    ].unwrap();
+let dataset = Dataset::from_dataframe(df, String::from("label")).unwrap();
+```
+"##
     )]
     #[cfg(feature = "dataframe")]
-    pub fn from_dataframe(mut dataframe: DataFrame, label_column: String) -> Result<Self> {
-        let label_col_name = label_column.as_str();
-
-        let (m, n) = dataframe.shape();
+    pub fn from_dataframe(dataframe: DataFrame, label_column: String) -> Result<Self> {
+        Self::from_dataframe_with_params(
+            &dataframe,
+            &label_column,
+            None,
+            None,
+            &DatasetParameters::default(),
+            None,
+        )
+    }
 
-        let label_series = &dataframe.select_series(label_col_name)?[0].cast::<Float32Type>()?;
+    /// Create a new `Dataset` from a polars DataFrame with optional weight and query group
+    /// columns and construction parameters, optionally sharing the bin mappers of `reference`.
+    ///
+    /// The group column holds an integer query id per row, and rows of a query must be
+    /// contiguous. `params` cannot set feature names, which are taken from the DataFrame.
+    /// Categories are encoded with the [`CategoricalEncoder`] of `reference`, if it has one.
+    ///
+    /// Note: the feature ```dataframe``` is required for this method
+    #[cfg(feature = "dataframe")]
+    pub fn from_dataframe_with_params(
+        dataframe: &DataFrame,
+        label_column: &str,
+        weight_column: Option<&str>,
+        group_column: Option<&str>,
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let feature_names = dataframe
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.to_string())
+            .filter(|name| {
+                name != label_column
+                    && Some(name.as_str()) != weight_column
+                    && Some(name.as_str()) != group_column
+            })
+            .collect::<Vec<_>>();
+        let label = dataframe::f32_column(dataframe, label_column)?;
+        let encoder = match reference.and_then(|r| r.categorical_encoder.as_ref()) {
+            Some(encoder) => encoder.clone(),
+            None => dataframe::fit_categories(dataframe, &feature_names)?,
+        };
+        let data = dataframe::feature_matrix(dataframe, &feature_names, Some(&encoder))?;
+        let params = params.with_table_columns(feature_names, encoder.categorical_feature())?;
 
-        if label_series.null_count() != 0 {
-            panic!("Cannot create a dataset with null values, encountered nulls when creating the label array")
+        let mut dataset =
+            Self::create_from_columns(&data, dataframe.height(), &label, &params, reference)?;
+        if !encoder.categorical_feature().is_empty() {
+            dataset.categorical_encoder = Some(encoder);
+        }
+        if let Some(weight_column) = weight_column {
+            dataset.set_weight(&dataframe::f32_column(dataframe, weight_column)?)?;
+        }
+        if let Some(group_column) = group_column {
+            dataset.set_group(&dataframe::group_sizes(dataframe, group_column)?)?;
         }
+        Ok(dataset)
+    }
 
-        dataframe.drop_in_place(label_col_name)?;
+    /// Create a new `Dataset` from an Arrow `RecordBatch`, using every other column as a feature.
+    ///
+    /// Numeric and boolean columns are features with nulls as missing values. Dictionary columns
+    /// are categorical features: their values are recorded in a [`CategoricalEncoder`], in order
+    /// of first appearance, and encoded by value, with nulls of the keys or of the dictionary as
+    /// missing values. Boosters trained on the dataset keep the encoder, so prediction uses the
+    /// same codes whatever the dictionary of its batch. Feature names are the column names.
+    ///
+    /// The bundled LightGBM has no Arrow C API, so the columns are copied once.
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    ///
+    /// Example
+    ///
+    #[cfg_attr(
+        feature = "arrow",
+        doc = r##"
+```
+extern crate arrow;
 
-        let mut label_values = Vec::with_capacity(m);
+use arrow::array::{ArrayRef, DictionaryArray, Float64Array};
+use arrow::datatypes::Int32Type;
+use arrow::record_batch::RecordBatch;
+use lightgbm::Dataset;
+use std::sync::Arc;
 
-        let label_values_ca = label_series.unpack::<Float32Type>()?;
+let color: DictionaryArray<Int32Type> = vec!["red", "blue", "red"].into_iter().collect();
+let batch = RecordBatch::try_from_iter(vec![
+    ("size", Arc::new(Float64Array::from(vec![Some(1.0), None, Some(0.1)])) as ArrayRef),
+    ("color", Arc::new(color) as ArrayRef),
+    ("label", Arc::new(Float64Array::from(vec![0.0, 0.0, 1.0])) as ArrayRef),
+]).unwrap();
+let dataset = Dataset::from_record_batch(&batch, "label").unwrap();
+```
+"##
+    )]
+    #[cfg(feature = "arrow")]
+    pub fn from_record_batch(batch: &RecordBatch, label_column: &str) -> Result<Self> {
+        Self::from_record_batch_with_params(
+            batch,
+            label_column,
+            None,
+            None,
+            &DatasetParameters::default(),
+            None,
+        )
+    }
 
-        label_values_ca
-            .into_no_null_iter()
-            .enumerate()
-            .for_each(|(_row_idx, val)| {
-                label_values.push(val);
-            });
+    /// Create a new `Dataset` from an Arrow `RecordBatch` with optional weight and query group
+    /// columns and construction parameters, optionally sharing the bin mappers of `reference`.
+    ///
+    /// The group column holds an integer query id per row, and rows of a query must be
+    /// contiguous. `params` cannot set feature names, which are taken from the batch.
+    /// Categories are encoded with the [`CategoricalEncoder`] of `reference`, if it has one.
+    ///
+    /// Note: the feature ```arrow``` is required for this method
+    #[cfg(feature = "arrow")]
+    pub fn from_record_batch_with_params(
+        batch: &RecordBatch,
+        label_column: &str,
+        weight_column: Option<&str>,
+        group_column: Option<&str>,
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let feature_names = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .filter(|name| {
+                name != label_column
+                    && Some(name.as_str()) != weight_column
+                    && Some(name.as_str()) != group_column
+            })
+            .collect::<Vec<_>>();
+        let label = record_batch::f32_column(batch, label_column)?;
+        let encoder = match reference.and_then(|r| r.categorical_encoder.as_ref()) {
+            Some(encoder) => encoder.clone(),
+            None => record_batch::fit_categories(batch, &feature_names)?,
+        };
+        let data = record_batch::feature_matrix(batch, &feature_names, Some(&encoder))?;
+        let params = params.with_table_columns(feature_names, encoder.categorical_feature())?;
 
-        let mut feature_values = Vec::with_capacity(m);
-        for _i in 0..m {
-            feature_values.push(Vec::with_capacity(n));
+        let mut dataset =
+            Self::create_from_columns(&data, batch.num_rows(), &label, &params, reference)?;
+        if !encoder.categorical_feature().is_empty() {
+            dataset.categorical_encoder = Some(encoder);
+        }
+        if let Some(weight_column) = weight_column {
+            dataset.set_weight(&record_batch::f32_column(batch, weight_column)?)?;
         }
+        if let Some(group_column) = group_column {
+            dataset.set_group(&record_batch::group_sizes(batch, group_column)?)?;
+        }
+        Ok(dataset)
+    }
 
-        for (_col_idx, series) in dataframe.get_columns().iter().enumerate() {
-            if series.null_count() != 0 {
-                panic!("Cannot create a dataset with null values, encountered nulls when creating the features array")
-            }
+    /// Create a `Dataset` from a column-major buffer holding a column per feature of `params`.
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    fn create_from_columns(
+        data: &[f64],
+        num_rows: usize,
+        label: &[f32],
+        params: &DatasetParameters,
+        reference: Option<&Dataset>,
+    ) -> Result<Self> {
+        let matrix = MatrixView::new(
+            data,
+            num_rows,
+            params.feature_names().len(),
+            MatrixLayout::ColumnMajor,
+        )?;
+        Self::from_slice_with_params(matrix, label, params, reference)
+    }
 
-            let series = series.cast::<Float64Type>()?;
-            let ca = series.unpack::<Float64Type>()?;
+    /// Save the `Dataset` to a LightGBM binary file.
+    ///
+    /// The binary file keeps the binned data, feature names and metadata, so it can be loaded
+    /// again with [`Dataset::from_binary_file`] without the cost of binning, or with
+    /// [`Dataset::from_binary_file_with_reference`] for validation data.
+    pub fn save_binary(&self, filename: &str) -> Result<()> {
+        let filename_str = CString::new(filename)?;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetSaveBinary(
+            self.handle,
+            filename_str.as_ptr() as *const c_char
+        ))?;
+        Ok(())
+    }
 
-            ca.into_no_null_iter()
-                .enumerate()
-                .for_each(|(row_idx, val)| feature_values[row_idx].push(val));
+    /// Get the number of data (rows) of the `Dataset`.
+    pub fn num_data(&self) -> Result<i32> {
+        let mut out = 0;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetGetNumData(self.handle, &mut out))?;
+        Ok(out)
+    }
+
+    /// Get the number of features of the `Dataset`.
+    pub fn num_feature(&self) -> Result<i32> {
+        let mut out = 0;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetGetNumFeature(
+            self.handle,
+            &mut out
+        ))?;
+        Ok(out)
+    }
+
+    /// Get the feature names of the `Dataset`.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1], vec![0.7, 0.4]];
+    /// let label = vec![0.0, 1.0];
+    /// let dataset = Dataset::from_mat(data, label).unwrap();
+    /// assert_eq!(dataset.feature_names().unwrap(), vec!["Column_0", "Column_1"]);
+    /// ```
+    pub fn feature_names(&self) -> Result<Vec<String>> {
+        read_string_array(|len, out_len, buffer_len, out_buffer_len, out_strs| {
+            lgbm_call!(lightgbm_sys::LGBM_DatasetGetFeatureNames(
+                self.handle,
+                len,
+                out_len,
+                buffer_len,
+                out_buffer_len,
+                out_strs
+            ))
+        })
+    }
+
+    /// Set the feature names of the `Dataset`.
+    ///
+    /// The number of names must match the number of features.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1], vec![0.7, 0.4]];
+    /// let label = vec![0.0, 1.0];
+    /// let mut dataset = Dataset::from_mat(data, label).unwrap();
+    /// dataset.set_feature_names(&["age", "height"]).unwrap();
+    /// assert_eq!(dataset.feature_names().unwrap(), vec!["age", "height"]);
+    /// ```
+    pub fn set_feature_names<S: AsRef<str>>(&mut self, feature_names: &[S]) -> Result<()> {
+        let feature_names_cstr = feature_names
+            .iter()
+            .map(|name| CString::new(name.as_ref()).map_err(Error::from))
+            .collect::<Result<Vec<_>>>()?;
+        let mut feature_names_ptr = feature_names_cstr
+            .iter()
+            .map(|name| name.as_ptr() as *const c_char)
+            .collect::<Vec<_>>();
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetSetFeatureNames(
+            self.handle,
+            feature_names_ptr.as_mut_ptr(),
+            feature_names_ptr.len() as i32
+        ))?;
+        Ok(())
+    }
+
+    /// Get the label of each row.
+    pub fn label(&self) -> Result<Vec<f32>> {
+        Ok(self
+            .get_field("label", lightgbm_sys::C_API_DTYPE_FLOAT32)?
+            .unwrap_or_default())
+    }
+
+    /// Get the query boundaries, the row offsets of the query groups followed by the number of
+    /// rows, or `None` when no query groups are set.
+    pub(crate) fn query_boundaries(&self) -> Result<Option<Vec<i32>>> {
+        self.get_field("group", lightgbm_sys::C_API_DTYPE_INT32)
+    }
+
+    fn get_field<T: Copy>(&self, field_name: &str, dtype: u32) -> Result<Option<Vec<T>>> {
+        let field_name_str = CString::new(field_name)?;
+        let mut out_len = 0;
+        let mut out_ptr = std::ptr::null();
+        let mut out_type = 0;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetGetField(
+            self.handle,
+            field_name_str.as_ptr() as *const c_char,
+            &mut out_len,
+            &mut out_ptr,
+            &mut out_type
+        ))?;
+        if out_ptr.is_null() {
+            return Ok(None);
         }
-        Self::from_mat(feature_values, label_values)
+        if out_type != dtype as i32 {
+            return Err(Error::invalid_input(format!(
+                "unexpected type {} of field {}",
+                out_type, field_name
+            )));
+        }
+        let values = unsafe { std::slice::from_raw_parts(out_ptr as *const T, out_len as usize) };
+        Ok(Some(values.to_vec()))
+    }
+
+    /// Set the label of each row.
+    pub fn set_label(&mut self, label: &[f32]) -> Result<()> {
+        self.set_field(
+            "label",
+            label.as_ptr() as *const c_void,
+            label.len(),
+            lightgbm_sys::C_API_DTYPE_FLOAT32,
+        )
+    }
+
+    /// Set the weight of each row.
+    pub fn set_weight(&mut self, weight: &[f32]) -> Result<()> {
+        self.set_field(
+            "weight",
+            weight.as_ptr() as *const c_void,
+            weight.len(),
+            lightgbm_sys::C_API_DTYPE_FLOAT32,
+        )
+    }
+
+    /// Set the query groups used for ranking, given as the sizes of consecutive row groups.
+    ///
+    /// Example
+    /// ```
+    /// use lightgbm::Dataset;
+    ///
+    /// let data = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8], vec![0.2, 0.2]];
+    /// let label = vec![0.0, 1.0, 2.0, 0.0];
+    /// let mut dataset = Dataset::from_mat(data, label).unwrap();
+    /// // the first query has 3 rows, the second one has 1 row
+    /// dataset.set_group(&[3, 1]).unwrap();
+    /// ```
+    pub fn set_group(&mut self, group: &[i32]) -> Result<()> {
+        self.set_field(
+            "group",
+            group.as_ptr() as *const c_void,
+            group.len(),
+            lightgbm_sys::C_API_DTYPE_INT32,
+        )
+    }
+
+    fn set_field(
+        &mut self,
+        field_name: &str,
+        data: *const c_void,
+        num_element: usize,
+        dtype: u32,
+    ) -> Result<()> {
+        let field_name_str = CString::new(field_name)?;
+        lgbm_call!(lightgbm_sys::LGBM_DatasetSetField(
+            self.handle,
+            field_name_str.as_ptr() as *const c_char,
+            data,
+            num_element as i32,
+            dtype as i32
+        ))?;
+        Ok(())
     }
 }
 
 impl Drop for Dataset {
     fn drop(&mut self) {
-        lgbm_call!(lightgbm_sys::LGBM_DatasetFree(self.handle)).unwrap();
+        // Freeing cannot fail for a valid handle, and a destructor cannot report an error.
+        let _ = lgbm_call!(lightgbm_sys::LGBM_DatasetFree(self.handle));
     }
 }
 
+// A LightGBM dataset is not tied to the thread that created it, and only the methods taking
+// `&mut self` write to it.
+unsafe impl Send for Dataset {}
+unsafe impl Sync for Dataset {}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
-    fn read_train_file() -> Result<Dataset> {
-        Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
+    use std::fs;
+    use std::path::Path;
+
+    /// The training data of LightGBM's binary classification example, shared by the tests.
+    pub(crate) fn read_train_file() -> Result<Dataset> {
+        Dataset::from_file("lightgbm-sys/lightgbm/examples/binary_classification/binary.train")
     }
 
     #[test]
@@ -211,6 +1025,57 @@ mod tests {
         assert!(read_train_file().is_ok());
     }
 
+    #[test]
+    fn save_and_load_binary() {
+        let dataset = read_train_file().unwrap();
+        let filename = "./test/test_save_binary.output.bin";
+        dataset.save_binary(filename).unwrap();
+        assert!(Path::new(filename).exists());
+
+        let binned = Dataset::from_binary_file(filename).unwrap();
+        assert_eq!(binned.num_data(), dataset.num_data());
+        assert_eq!(binned.num_feature(), dataset.num_feature());
+        assert_eq!(binned.feature_names(), dataset.feature_names());
+        let _ = fs::remove_file(filename);
+
+        assert!(Dataset::from_binary_file(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.train"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn binary_validation_data() {
+        use crate::Booster;
+        use serde_json::json;
+
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        let filename = "./test/test_binary_validation_data.output.bin";
+        valid.save_binary(filename).unwrap();
+
+        let binned = Dataset::from_binary_file_with_reference(filename, &train).unwrap();
+        assert!(binned.shares_bin_mappers_with(&train));
+        assert_eq!(binned.num_data(), valid.num_data());
+        let mut bst =
+            Booster::create(train, &json!({"objective": "binary", "metric": "auc"})).unwrap();
+        bst.add_valid_data(binned).unwrap();
+        assert!(!bst.update_one_iter().unwrap());
+        assert_eq!(bst.eval(1).unwrap().len(), 1);
+        let _ = fs::remove_file(filename);
+
+        let train = read_train_file().unwrap();
+        assert!(Dataset::from_binary_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train
+        )
+        .is_err());
+    }
+
     #[test]
     fn from_mat() {
         let data = vec![
@@ -225,6 +1090,304 @@ mod tests {
         assert!(dataset.is_ok());
     }
 
+    #[test]
+    fn with_reference() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        assert!(valid.shares_bin_mappers_with(&train));
+
+        let data = vec![vec![0.5; 28], vec![0.1; 28]];
+        let label = vec![0.0, 1.0];
+        let valid = Dataset::from_mat_with_reference(data.clone(), label.clone(), &train).unwrap();
+        assert!(valid.shares_bin_mappers_with(&train));
+        let other = Dataset::from_mat(data, label).unwrap();
+        assert!(!other.shares_bin_mappers_with(&train));
+    }
+
+    #[test]
+    fn subset() {
+        let dataset = read_train_file().unwrap();
+        let subset = dataset.subset(&[0, 2, 4, 6]).unwrap();
+        assert_eq!(subset.num_data(), Ok(4));
+        assert_eq!(subset.num_feature(), dataset.num_feature());
+        assert!(subset.shares_bin_mappers_with(&dataset));
+        assert_eq!(subset.subset(&[1, 3]).unwrap().num_data(), Ok(2));
+
+        assert!(dataset.subset(&[]).is_err());
+        assert!(dataset.subset(&[2, 1]).is_err());
+        assert!(dataset.subset(&[1, 1]).is_err());
+        assert!(dataset.subset(&[-1]).is_err());
+        assert!(dataset.subset(&[7000]).is_err());
+    }
+
+    #[test]
+    fn concurrent_use() {
+        let dataset = read_train_file().unwrap();
+        let label = dataset.label().unwrap();
+        let feature_names = dataset.feature_names().unwrap();
+        std::thread::scope(|scope| {
+            for worker in 0..8 {
+                let (dataset, label, feature_names) = (&dataset, &label, &feature_names);
+                scope.spawn(move || {
+                    for i in 0..20 {
+                        let rows = (worker + i..7000).step_by(97).collect::<Vec<i32>>();
+                        let subset = dataset.subset(&rows).unwrap();
+                        assert_eq!(subset.num_data(), Ok(rows.len() as i32));
+                        assert!(subset.shares_bin_mappers_with(dataset));
+                        assert_eq!(&dataset.label().unwrap(), label);
+                        assert_eq!(&dataset.feature_names().unwrap(), feature_names);
+                    }
+                    let valid = Dataset::from_mat_with_reference(
+                        vec![vec![0.5; 28], vec![worker as f64; 28]],
+                        vec![0.0, 1.0],
+                        dataset,
+                    )
+                    .unwrap();
+                    assert_eq!(valid.num_data(), Ok(2));
+                });
+            }
+        });
+
+        // a dataset can also move to another thread
+        let handle = std::thread::spawn(move || dataset.num_data().unwrap());
+        assert_eq!(handle.join().unwrap(), 7000);
+    }
+
+    #[test]
+    fn from_slice() {
+        let data = vec![1.0_f32, 0.1, 0.7, 0.4, 0.9, 0.8];
+        let label = vec![0.0, 0.0, 1.0];
+        let matrix = MatrixView::new(&data, 3, 2, MatrixLayout::RowMajor).unwrap();
+        let dataset = Dataset::from_slice(matrix, &label).unwrap();
+        assert_eq!(dataset.num_data(), Ok(3));
+        assert_eq!(dataset.num_feature(), Ok(2));
+
+        let matrix = MatrixView::new(&data, 2, 3, MatrixLayout::ColumnMajor).unwrap();
+        assert!(Dataset::from_slice(matrix, &label).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn from_array() {
+        use ndarray::{arr1, Array2, ShapeBuilder};
+        let label = arr1(&[0.0_f32, 0.0, 1.0, 1.0]);
+        let c_order = Array2::<f64>::from_elem((4, 3), 0.5);
+        let dataset = Dataset::from_array(c_order.view(), label.view()).unwrap();
+        assert_eq!(dataset.num_data(), Ok(4));
+        assert_eq!(dataset.num_feature(), Ok(3));
+
+        let f_order = Array2::<f32>::from_elem((4, 3).f(), 0.5);
+        assert!(Dataset::from_array(f_order.view(), label.view()).is_ok());
+
+        let strided = Array2::<f64>::from_elem((8, 3), 0.5);
+        let every_other_row = strided.slice(ndarray::s![..;2, ..]);
+        assert!(Dataset::from_array(every_other_row, label.view()).is_ok());
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn from_record_batch() {
+        use arrow::array::{ArrayRef, DictionaryArray, Float32Array, Int64Array, StringArray};
+        use arrow::datatypes::Int32Type;
+        use std::sync::Arc;
+
+        let color: DictionaryArray<Int32Type> = vec![Some("a"), None, Some("b"), Some("a")]
+            .into_iter()
+            .collect();
+        let columns = vec![
+            (
+                "x",
+                Arc::new(Float32Array::from(vec![
+                    Some(1.0),
+                    None,
+                    Some(0.2),
+                    Some(0.1),
+                ])) as ArrayRef,
+            ),
+            ("color", Arc::new(color) as ArrayRef),
+            (
+                "weight",
+                Arc::new(Float32Array::from(vec![1.0, 0.5, 1.0, 2.0])) as ArrayRef,
+            ),
+            (
+                "query",
+                Arc::new(Int64Array::from(vec![1, 1, 2, 2])) as ArrayRef,
+            ),
+            (
+                "label",
+                Arc::new(Float32Array::from(vec![0.0, 1.0, 1.0, 0.0])) as ArrayRef,
+            ),
+        ];
+        let batch = RecordBatch::try_from_iter(columns.clone()).unwrap();
+        let dataset = Dataset::from_record_batch_with_params(
+            &batch,
+            "label",
+            Some("weight"),
+            Some("query"),
+            &DatasetParameters::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(dataset.num_data(), Ok(4));
+        assert_eq!(
+            dataset.feature_names(),
+            Ok(vec!["x".to_string(), "color".to_string()])
+        );
+
+        let encoder = dataset.categorical_encoder.clone().unwrap();
+        assert_eq!(encoder.categories(), &[vec!["a", "b"]]);
+        let valid = Dataset::from_record_batch_with_params(
+            &batch.project(&[0, 1, 4]).unwrap(),
+            "label",
+            None,
+            None,
+            &DatasetParameters::default(),
+            Some(&dataset),
+        )
+        .unwrap();
+        assert_eq!(valid.categorical_encoder, Some(encoder));
+
+        let plain = Dataset::from_record_batch(&batch, "label").unwrap();
+        assert_eq!(plain.num_feature(), Ok(4));
+        assert!(Dataset::from_record_batch(&batch, "target").is_err());
+
+        let mut columns = columns;
+        columns.push((
+            "name",
+            Arc::new(StringArray::from(vec!["a", "b", "c", "d"])) as ArrayRef,
+        ));
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        assert!(Dataset::from_record_batch(&batch, "label").is_err());
+    }
+
+    #[test]
+    fn from_mat_with_feature_names() {
+        let data = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+        let label = vec![0.0, 0.0, 1.0];
+        let dataset =
+            Dataset::from_mat_with_feature_names(data.clone(), label.clone(), &["a", "b"]).unwrap();
+        assert_eq!(dataset.feature_names().unwrap(), vec!["a", "b"]);
+        assert!(Dataset::from_mat_with_feature_names(data, label, &["a"]).is_err());
+    }
+
+    #[test]
+    fn from_mat_with_categorical_feature() {
+        let data = vec![
+            vec![1.0, 0.1, 2.0],
+            vec![0.7, 0.4, 0.0],
+            vec![0.1, 0.7, f64::NAN],
+        ];
+        let label = vec![0.0, 0.0, 1.0];
+        let names = ["a", "b", "color"];
+        let categorical = vec![CategoricalFeature::from("color")];
+        let dataset = Dataset::from_mat_with_categorical_feature(
+            data.clone(),
+            label.clone(),
+            &names,
+            &categorical,
+        )
+        .unwrap();
+        assert_eq!(dataset.feature_names().unwrap(), names);
+
+        let categorical = vec![CategoricalFeature::from(1)];
+        assert!(
+            Dataset::from_mat_with_categorical_feature(data, label, &names, &categorical).is_err()
+        );
+    }
+
+    #[test]
+    fn with_params() {
+        let params = DatasetParametersBuilder::default()
+            .max_bin(15)
+            .min_data_in_bin(1)
+            .build()
+            .unwrap();
+        let dataset = Dataset::from_file_with_params(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.train",
+            &params,
+            None,
+        );
+        assert!(dataset.is_ok());
+
+        let data = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+        let label = vec![0.0, 0.0, 1.0];
+        let dataset = Dataset::from_mat_with_params(data.clone(), label.clone(), &params, None);
+        assert!(dataset.is_ok());
+
+        let params = DatasetParametersBuilder::default()
+            .max_bin(0)
+            .build()
+            .unwrap();
+        assert!(Dataset::from_mat_with_params(data, label, &params, None).is_err());
+    }
+
+    #[test]
+    fn from_row_iter() {
+        let rows = (0..10).map(|i| (vec![i as f64, (i % 3) as f64], (i % 2) as f32));
+        let params = DatasetParametersBuilder::default()
+            .bin_construct_sample_cnt(4)
+            .feature_names(vec!["x".to_string(), "y".to_string()])
+            .build()
+            .unwrap();
+        let dataset = Dataset::from_row_iter(rows.clone(), 10, &params).unwrap();
+        assert_eq!(dataset.num_data(), Ok(10));
+        assert_eq!(
+            dataset.feature_names(),
+            Ok(vec!["x".to_string(), "y".to_string()])
+        );
+
+        assert!(Dataset::from_row_iter(rows.clone(), 11, &params).is_err());
+        assert!(Dataset::from_row_iter(rows, 9, &params).is_err());
+    }
+
+    #[test]
+    fn set_weight_and_group() {
+        let data = vec![vec![1.0, 0.1], vec![0.7, 0.4], vec![0.9, 0.8]];
+        let label = vec![0.0, 0.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+        assert!(dataset.set_weight(&[1.0, 0.5, 2.0]).is_ok());
+        assert!(dataset.set_weight(&[1.0]).is_err());
+        assert!(dataset.set_group(&[2, 1]).is_ok());
+        assert!(dataset.set_group(&[2, 2]).is_err());
+
+        assert_eq!(dataset.label(), Ok(vec![0.0, 0.0, 1.0]));
+        assert_eq!(dataset.query_boundaries(), Ok(Some(vec![0, 2, 3])));
+        let dataset = Dataset::from_mat(vec![vec![1.0]], vec![1.0]).unwrap();
+        assert_eq!(dataset.query_boundaries(), Ok(None));
+    }
+
+    #[test]
+    fn feature_names() {
+        let data = vec![
+            vec![1.0, 0.1, 0.2],
+            vec![0.7, 0.4, 0.5],
+            vec![0.9, 0.8, 0.5],
+        ];
+        let label = vec![0.0, 0.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+        assert_eq!(
+            dataset.feature_names().unwrap(),
+            vec!["Column_0", "Column_1", "Column_2"]
+        );
+
+        let long_name = "a_very_long_feature_name_".repeat(8);
+        let names = vec![long_name.as_str(), "身長", "poids_é"];
+        dataset.set_feature_names(&names).unwrap();
+        assert_eq!(dataset.feature_names().unwrap(), names);
+    }
+
+    #[test]
+    fn set_feature_names_length_mismatch() {
+        let data = vec![vec![1.0, 0.1], vec![0.7, 0.4]];
+        let label = vec![0.0, 1.0];
+        let mut dataset = Dataset::from_mat(data, label).unwrap();
+        assert!(dataset.set_feature_names(&["only_one"]).is_err());
+    }
+
     #[cfg(feature = "dataframe")]
     #[test]
     fn from_dataframe() {
@@ -241,4 +1404,62 @@ mod tests {
         let df_dataset = Dataset::from_dataframe(df, String::from("label"));
         assert!(df_dataset.is_ok());
     }
+
+    #[cfg(feature = "dataframe")]
+    #[test]
+    fn from_dataframe_with_params() {
+        use polars::df;
+        let df: DataFrame = df![
+            "x" => [Some(1.0), None, Some(0.2), Some(0.1)],
+            "color" => [Some("a"), None, Some("b"), Some("a")],
+            "weight" => [1.0, 0.5, 1.0, 2.0],
+            "query" => [1, 1, 2, 2],
+            "label" => [0.0, 1.0, 1.0, 0.0]
+        ]
+        .unwrap();
+        let dataset = Dataset::from_dataframe_with_params(
+            &df,
+            "label",
+            Some("weight"),
+            Some("query"),
+            &DatasetParameters::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(dataset.num_data(), Ok(4));
+        assert_eq!(
+            dataset.feature_names(),
+            Ok(vec!["x".to_string(), "color".to_string()])
+        );
+        let encoder = dataset.categorical_encoder.clone().unwrap();
+        assert_eq!(encoder.categories(), &[vec!["a", "b"]]);
+
+        // A validation frame is encoded with the categories of its reference.
+        let valid = df!["x" => [0.5], "color" => ["b"], "label" => [1.0]].unwrap();
+        let valid = Dataset::from_dataframe_with_params(
+            &valid,
+            "label",
+            None,
+            None,
+            &DatasetParameters::default(),
+            Some(&dataset),
+        )
+        .unwrap();
+        assert_eq!(valid.categorical_encoder, Some(encoder));
+
+        let with_names = DatasetParametersBuilder::default()
+            .feature_names(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .unwrap();
+        assert!(Dataset::from_dataframe_with_params(
+            &df,
+            "label",
+            Some("weight"),
+            Some("query"),
+            &with_names,
+            None
+        )
+        .is_err());
+        assert!(Dataset::from_dataframe(df, String::from("target")).is_err());
+    }
 }
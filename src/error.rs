@@ -1,8 +1,11 @@
 //! Functionality related to errors and error handling.
 
 use std::error;
-use std::ffi::CStr;
+use std::ffi::{CStr, NulError};
 use std::fmt::{self, Debug, Display};
+use std::io;
+use std::mem;
+use std::str::Utf8Error;
 
 use lightgbm_sys;
 
@@ -12,65 +15,171 @@ use polars::prelude::*;
 /// Convenience return type for most operations which can return an `LightGBM`.
 pub type Result<T> = std::result::Result<T, Error>;
 
-/// Wrap errors returned by the LightGBM library.
-#[derive(Debug, Eq, PartialEq)]
-pub struct Error {
-    desc: String,
+/// Errors returned by the crate.
+///
+/// Two errors compare equal when they are of the same kind and have the same message.
+#[derive(Debug)]
+pub enum Error {
+    /// A call to the LightGBM C API failed.
+    LightGBM {
+        /// Name of the failing C API function.
+        call: &'static str,
+        /// Last error message of LightGBM.
+        message: String,
+    },
+    /// Input data has an invalid shape, type or value.
+    InvalidInput(String),
+    /// Parameters are invalid or inconsistent.
+    InvalidParameter(String),
+    /// A file cannot be read or written.
+    Io {
+        /// Path of the file.
+        path: String,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// A string returned by LightGBM is not valid UTF-8.
+    Utf8(Utf8Error),
+    /// A polars operation failed.
+    #[cfg(feature = "dataframe")]
+    DataFrame(PolarsError),
 }
 
 impl Error {
-    pub(crate) fn new<S: Into<String>>(desc: S) -> Self {
-        Self { desc: desc.into() }
+    pub(crate) fn invalid_input<S: Into<String>>(message: S) -> Self {
+        Error::InvalidInput(message.into())
     }
 
-    /// Check the return value from an LightGBM FFI call, and return the last error message on error.
+    pub(crate) fn invalid_parameter<S: Into<String>>(message: S) -> Self {
+        Error::InvalidParameter(message.into())
+    }
+
+    pub(crate) fn io<S: Into<String>>(path: S, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// Check the return value from the LightGBM FFI function `call`, and return the last error
+    /// message on error.
     ///
     /// Return values of 0 are treated as success, returns values of -1 are treated as errors.
-    ///
-    /// Meaning of any other return values are undefined, and will cause a panic.
-    pub(crate) fn check_return_value(ret_val: i32) -> Result<()> {
+    pub(crate) fn check_return_value(ret_val: i32, call: &'static str) -> Result<()> {
         match ret_val {
             0 => Ok(()),
-            -1 => Err(Self::from_lightgbm()),
-            _ => panic!("unexpected return value '{}', expected 0 or -1", ret_val),
+            -1 => Err(Self::from_lightgbm(call)),
+            _ => Err(Error::LightGBM {
+                call,
+                message: format!("unexpected return value '{}', expected 0 or -1", ret_val),
+            }),
         }
     }
 
     /// Get the last error message from LightGBM.
-    fn from_lightgbm() -> Self {
+    fn from_lightgbm(call: &'static str) -> Self {
         let c_str = unsafe { CStr::from_ptr(lightgbm_sys::LGBM_GetLastError()) };
-        let str_slice = c_str.to_str().unwrap();
-        Self::new(str_slice)
+        Error::LightGBM {
+            call,
+            message: c_str.to_string_lossy().into_owned(),
+        }
     }
 }
 
-impl error::Error for Error {}
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        mem::discriminant(self) == mem::discriminant(other) && self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for Error {}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Utf8(e) => Some(e),
+            #[cfg(feature = "dataframe")]
+            Error::DataFrame(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "LightGBM error: {}", &self.desc)
+        match self {
+            Error::LightGBM { call, message } => {
+                write!(f, "LightGBM error in {}: {}", call, message)
+            }
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::InvalidParameter(message) => write!(f, "invalid parameter: {}", message),
+            Error::Io { path, source } => write!(f, "cannot access {}: {}", path, source),
+            Error::Utf8(e) => write!(f, "invalid UTF-8 string: {}", e),
+            #[cfg(feature = "dataframe")]
+            Error::DataFrame(e) => write!(f, "polars error: {}", e),
+        }
+    }
+}
+
+impl From<NulError> for Error {
+    fn from(e: NulError) -> Self {
+        Error::invalid_input(format!(
+            "string contains a NUL byte at position {}",
+            e.nul_position()
+        ))
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::Utf8(e)
     }
 }
 
 #[cfg(feature = "dataframe")]
 impl From<PolarsError> for Error {
     fn from(pe: PolarsError) -> Self {
-        Self {
-            desc: pe.to_string(),
-        }
+        Error::DataFrame(pe)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::ffi::CString;
 
     #[test]
     fn return_value_handling() {
-        let result = Error::check_return_value(0);
+        let result = Error::check_return_value(0, "LGBM_Test");
         assert_eq!(result, Ok(()));
 
-        let result = Error::check_return_value(-1);
-        assert_eq!(result, Err(Error::new("Everything is fine")));
+        let result = Error::check_return_value(-1, "LGBM_Test");
+        assert_eq!(
+            result,
+            Err(Error::LightGBM {
+                call: "LGBM_Test",
+                message: String::from("Everything is fine")
+            })
+        );
+
+        let result = Error::check_return_value(1, "LGBM_Test");
+        assert!(matches!(result, Err(Error::LightGBM { .. })));
+    }
+
+    #[test]
+    fn kinds() {
+        let nul: Error = CString::new("a\0b").unwrap_err().into();
+        assert_eq!(
+            nul,
+            Error::invalid_input("string contains a NUL byte at position 1")
+        );
+        assert_ne!(
+            Error::invalid_input("message"),
+            Error::invalid_parameter("message")
+        );
+        let io_error = Error::io("file", io::Error::new(io::ErrorKind::NotFound, "not found"));
+        assert_eq!(io_error.to_string(), "cannot access file: not found");
+        assert!(error::Error::source(&io_error).is_some());
     }
 }
@@ -0,0 +1,533 @@
+//! Estimators for classification, regression and ranking, on top of `Dataset` and `Booster`.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+
+use serde_json::{json, Value};
+
+use crate::utils::{append_model_line, read_model_line};
+use crate::{Booster, DType, Dataset, DatasetParameters, Error, MatrixView, Prediction, Result};
+
+/// Key of the model file line that holds the classes of a [`LGBMClassifier`].
+const CLASSES_LINE_KEY: &str = "classifier_classes:";
+
+/// A class label of a [`LGBMClassifier`].
+///
+/// Classes are sorted with [`ClassLabel::compare`] and mapped to `0..num_class` in that order.
+/// They are saved with the model as JSON values.
+pub trait ClassLabel: Clone + Debug + PartialEq {
+    /// Total order of the labels.
+    fn compare(&self, other: &Self) -> Ordering;
+    /// Convert the label to JSON, to save it with the model.
+    fn to_value(&self) -> Value;
+    /// Read a label saved with [`ClassLabel::to_value`].
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+macro_rules! impl_class_label {
+    ($as_json:ident, $($t:ty),*) => {$(
+        impl ClassLabel for $t {
+            fn compare(&self, other: &Self) -> Ordering {
+                self.cmp(other)
+            }
+
+            fn to_value(&self) -> Value {
+                json!(self)
+            }
+
+            fn from_value(value: &Value) -> Option<Self> {
+                value.$as_json().and_then(|v| <$t>::try_from(v).ok())
+            }
+        }
+    )*};
+}
+
+impl_class_label!(as_i64, i8, i16, i32, i64);
+impl_class_label!(as_u64, u8, u16, u32, u64);
+
+impl ClassLabel for f64 {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.total_cmp(other)
+    }
+
+    fn to_value(&self) -> Value {
+        json!(self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_f64()
+    }
+}
+
+impl ClassLabel for bool {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn to_value(&self) -> Value {
+        json!(self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_bool()
+    }
+}
+
+impl ClassLabel for String {
+    fn compare(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+
+    fn to_value(&self) -> Value {
+        json!(self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        value.as_str().map(String::from)
+    }
+}
+
+/// Training parameters and fitted booster shared by the estimators.
+struct Estimator {
+    params: Value,
+    dataset_params: DatasetParameters,
+    booster: Option<Booster>,
+}
+
+impl Estimator {
+    fn new(params: Value) -> Self {
+        Estimator {
+            params,
+            dataset_params: DatasetParameters::default(),
+            booster: None,
+        }
+    }
+
+    fn from_file(filename: &str) -> Result<Self> {
+        let mut estimator = Self::new(json!({}));
+        estimator.booster = Some(Booster::from_file(filename)?);
+        Ok(estimator)
+    }
+
+    /// The parameters, with the given values for the keys they do not set.
+    fn params_with_defaults(&self, defaults: Vec<(&str, Value)>) -> Result<Value> {
+        let mut params = self
+            .params
+            .as_object()
+            .cloned()
+            .ok_or_else(|| Error::invalid_parameter("parameters must be a JSON object"))?;
+        for (key, value) in defaults {
+            params.entry(key).or_insert(value);
+        }
+        Ok(Value::Object(params))
+    }
+
+    fn fit<T: DType>(
+        &mut self,
+        data: MatrixView<T>,
+        label: &[f32],
+        group: Option<&[i32]>,
+        params: &Value,
+    ) -> Result<()> {
+        let mut dataset = Dataset::from_slice_with_params(data, label, &self.dataset_params, None)?;
+        if let Some(group) = group {
+            dataset.set_group(group)?;
+        }
+        self.booster = Some(Booster::train(dataset, params)?);
+        Ok(())
+    }
+
+    fn booster(&self) -> Result<&Booster> {
+        self.booster
+            .as_ref()
+            .ok_or_else(|| Error::invalid_input("the estimator is not fitted"))
+    }
+
+    fn predict<T: DType>(&self, data: MatrixView<T>) -> Result<Prediction> {
+        self.booster()?.predict_slice(data)
+    }
+
+    fn save_file(&self, filename: &str) -> Result<()> {
+        self.booster()?.save_file(filename)
+    }
+}
+
+/// Classifier for labels of any [`ClassLabel`] type.
+///
+/// The classes are mapped to `0..num_class` for training. Unless the parameters set it, the
+/// objective is `binary` for two classes and `multiclass` with the matching `num_class` for
+/// more. The classes are saved with the model by [`LGBMClassifier::save_file`].
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{LGBMClassifier, MatrixLayout, MatrixView};
+/// use serde_json::json;
+///
+/// let data = vec![1.0, 0.1, 0.9, 0.2, 0.1, 0.8, 0.2, 0.9, 0.5, 0.5, 0.4, 0.6];
+/// let matrix = MatrixView::new(&data, 6, 2, MatrixLayout::RowMajor).unwrap();
+/// let label = ["cat", "cat", "dog", "dog", "bird", "bird"]
+///     .iter()
+///     .map(|s| s.to_string())
+///     .collect::<Vec<_>>();
+///
+/// let mut clf = LGBMClassifier::new(json!({"num_iterations": 5, "min_data_in_leaf": 1}));
+/// clf.fit(matrix, &label).unwrap();
+/// assert_eq!(clf.classes(), &["bird", "cat", "dog"]);
+/// let proba = clf.predict_proba(matrix).unwrap();
+/// assert_eq!(proba[0].len(), 3);
+/// let predicted = clf.predict(matrix).unwrap();
+/// ```
+pub struct LGBMClassifier<L: ClassLabel = i64> {
+    estimator: Estimator,
+    classes: Vec<L>,
+}
+
+impl<L: ClassLabel> LGBMClassifier<L> {
+    /// Create a classifier with the given training parameters.
+    pub fn new(params: Value) -> Self {
+        LGBMClassifier {
+            estimator: Estimator::new(params),
+            classes: Vec::new(),
+        }
+    }
+
+    /// Set the parameters of the training `Dataset`, such as categorical features.
+    pub fn with_dataset_params(mut self, dataset_params: DatasetParameters) -> Self {
+        self.estimator.dataset_params = dataset_params;
+        self
+    }
+
+    /// Load a classifier saved with [`LGBMClassifier::save_file`].
+    pub fn from_file(filename: &str) -> Result<Self> {
+        let invalid = || Error::invalid_input(format!("invalid classes in {}", filename));
+        let classes = read_model_line(filename, CLASSES_LINE_KEY)?
+            .ok_or_else(|| Error::invalid_input(format!("no classifier classes in {}", filename)))?
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|v| L::from_value(v).ok_or_else(invalid))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(LGBMClassifier {
+            estimator: Estimator::from_file(filename)?,
+            classes,
+        })
+    }
+
+    /// Train on a dense matrix with one label per row.
+    pub fn fit<T: DType>(&mut self, data: MatrixView<T>, label: &[L]) -> Result<()> {
+        let mut classes = label.to_vec();
+        classes.sort_by(|a, b| a.compare(b));
+        classes.dedup_by(|a, b| a.compare(b) == Ordering::Equal);
+        if classes.len() < 2 {
+            return Err(Error::invalid_input(
+                "classification needs at least two classes",
+            ));
+        }
+        let encoded = label
+            .iter()
+            .map(|l| {
+                classes
+                    .binary_search_by(|c| c.compare(l))
+                    .map(|code| code as f32)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let params = if classes.len() == 2 {
+            self.estimator
+                .params_with_defaults(vec![("objective", json!("binary"))])?
+        } else {
+            let params = self.estimator.params_with_defaults(vec![
+                ("objective", json!("multiclass")),
+                ("num_class", json!(classes.len())),
+            ])?;
+            if params["num_class"].as_u64() != Some(classes.len() as u64) {
+                return Err(Error::invalid_parameter(format!(
+                    "num_class {} does not match the {} classes of the label",
+                    params["num_class"],
+                    classes.len()
+                )));
+            }
+            params
+        };
+        self.estimator.fit(data, &encoded, None, &params)?;
+        self.classes = classes;
+        Ok(())
+    }
+
+    /// The classes, in the order of the columns of [`LGBMClassifier::predict_proba`].
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    /// The fitted booster, if any.
+    pub fn booster(&self) -> Option<&Booster> {
+        self.estimator.booster.as_ref()
+    }
+
+    /// Predict the probability of every class for each row.
+    pub fn predict_proba<T: DType>(&self, data: MatrixView<T>) -> Result<Vec<Vec<f64>>> {
+        let prediction = self.estimator.predict(data)?;
+        if prediction.num_outputs() == 1 && self.classes.len() == 2 {
+            Ok(prediction.rows().map(|p| vec![1.0 - p[0], p[0]]).collect())
+        } else if prediction.num_outputs() == self.classes.len() {
+            Ok(prediction.rows().map(|p| p.to_vec()).collect())
+        } else {
+            Err(Error::invalid_input(format!(
+                "the model outputs {} values per row for {} classes",
+                prediction.num_outputs(),
+                self.classes.len()
+            )))
+        }
+    }
+
+    /// Predict the most probable class of each row.
+    pub fn predict<T: DType>(&self, data: MatrixView<T>) -> Result<Vec<L>> {
+        Ok(self
+            .predict_proba(data)?
+            .iter()
+            .map(|p| {
+                let best = (0..p.len())
+                    .max_by(|&a, &b| p[a].total_cmp(&p[b]))
+                    .unwrap_or(0);
+                self.classes[best].clone()
+            })
+            .collect())
+    }
+
+    /// Save the model and its classes to file.
+    pub fn save_file(&self, filename: &str) -> Result<()> {
+        self.estimator.save_file(filename)?;
+        let classes = self.classes.iter().map(L::to_value).collect();
+        append_model_line(filename, CLASSES_LINE_KEY, &Value::Array(classes))
+    }
+}
+
+/// Regressor predicting one value per row; the objective is `regression` unless the
+/// parameters set it.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{LGBMRegressor, MatrixLayout, MatrixView};
+/// use serde_json::json;
+///
+/// let data = vec![1.0, 0.1, 0.9, 0.2, 0.1, 0.8, 0.2, 0.9];
+/// let matrix = MatrixView::new(&data, 4, 2, MatrixLayout::RowMajor).unwrap();
+/// let mut reg = LGBMRegressor::new(json!({"num_iterations": 5, "min_data_in_leaf": 1}));
+/// reg.fit(matrix, &[1.5, 1.2, 0.3, 0.1]).unwrap();
+/// let predicted = reg.predict(matrix).unwrap();
+/// ```
+pub struct LGBMRegressor {
+    estimator: Estimator,
+}
+
+impl LGBMRegressor {
+    /// Create a regressor with the given training parameters.
+    pub fn new(params: Value) -> Self {
+        LGBMRegressor {
+            estimator: Estimator::new(params),
+        }
+    }
+
+    /// Set the parameters of the training `Dataset`, such as categorical features.
+    pub fn with_dataset_params(mut self, dataset_params: DatasetParameters) -> Self {
+        self.estimator.dataset_params = dataset_params;
+        self
+    }
+
+    /// Load a regressor saved with [`LGBMRegressor::save_file`].
+    pub fn from_file(filename: &str) -> Result<Self> {
+        Ok(LGBMRegressor {
+            estimator: Estimator::from_file(filename)?,
+        })
+    }
+
+    /// Train on a dense matrix with one target per row.
+    pub fn fit<T: DType>(&mut self, data: MatrixView<T>, label: &[f32]) -> Result<()> {
+        let params = self
+            .estimator
+            .params_with_defaults(vec![("objective", json!("regression"))])?;
+        self.estimator.fit(data, label, None, &params)
+    }
+
+    /// The fitted booster, if any.
+    pub fn booster(&self) -> Option<&Booster> {
+        self.estimator.booster.as_ref()
+    }
+
+    /// Predict the target of each row.
+    pub fn predict<T: DType>(&self, data: MatrixView<T>) -> Result<Vec<f64>> {
+        Ok(self.estimator.predict(data)?.into_vec())
+    }
+
+    /// Save the model to file.
+    pub fn save_file(&self, filename: &str) -> Result<()> {
+        self.estimator.save_file(filename)
+    }
+}
+
+/// Ranker scoring rows within queries; the objective is `lambdarank` unless the parameters
+/// set it.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{LGBMRanker, MatrixLayout, MatrixView};
+/// use serde_json::json;
+///
+/// let data = vec![1.0, 0.1, 0.9, 0.2, 0.1, 0.8, 0.2, 0.9, 0.5, 0.5, 0.4, 0.6];
+/// let matrix = MatrixView::new(&data, 6, 2, MatrixLayout::RowMajor).unwrap();
+/// let mut ranker = LGBMRanker::new(json!({"num_iterations": 5, "min_data_in_leaf": 1}));
+/// ranker.fit(matrix, &[2.0, 1.0, 0.0, 2.0, 0.0, 1.0], &[3, 3]).unwrap();
+/// let scores = ranker.predict(matrix).unwrap();
+/// ```
+pub struct LGBMRanker {
+    estimator: Estimator,
+}
+
+impl LGBMRanker {
+    /// Create a ranker with the given training parameters.
+    pub fn new(params: Value) -> Self {
+        LGBMRanker {
+            estimator: Estimator::new(params),
+        }
+    }
+
+    /// Set the parameters of the training `Dataset`, such as categorical features.
+    pub fn with_dataset_params(mut self, dataset_params: DatasetParameters) -> Self {
+        self.estimator.dataset_params = dataset_params;
+        self
+    }
+
+    /// Load a ranker saved with [`LGBMRanker::save_file`].
+    pub fn from_file(filename: &str) -> Result<Self> {
+        Ok(LGBMRanker {
+            estimator: Estimator::from_file(filename)?,
+        })
+    }
+
+    /// Train on a dense matrix with one relevance label per row and the sizes of consecutive
+    /// queries, as in [`Dataset::set_group`].
+    pub fn fit<T: DType>(
+        &mut self,
+        data: MatrixView<T>,
+        label: &[f32],
+        group: &[i32],
+    ) -> Result<()> {
+        let params = self
+            .estimator
+            .params_with_defaults(vec![("objective", json!("lambdarank"))])?;
+        self.estimator.fit(data, label, Some(group), &params)
+    }
+
+    /// The fitted booster, if any.
+    pub fn booster(&self) -> Option<&Booster> {
+        self.estimator.booster.as_ref()
+    }
+
+    /// Predict the ranking score of each row.
+    pub fn predict<T: DType>(&self, data: MatrixView<T>) -> Result<Vec<f64>> {
+        Ok(self.estimator.predict(data)?.into_vec())
+    }
+
+    /// Save the model to file.
+    pub fn save_file(&self, filename: &str) -> Result<()> {
+        self.estimator.save_file(filename)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MatrixLayout;
+    use std::fs;
+
+    fn _data() -> Vec<f64> {
+        (0..40)
+            .flat_map(|i| vec![(i % 4) as f64, (i % 5) as f64 * 0.1])
+            .collect()
+    }
+
+    fn _params() -> Value {
+        json!({"num_iterations": 5, "min_data_in_leaf": 1, "min_data_in_bin": 1})
+    }
+
+    #[test]
+    fn binary_classifier() {
+        let data = _data();
+        let matrix = MatrixView::new(&data, 40, 2, MatrixLayout::RowMajor).unwrap();
+        let label = (0..40).map(|i| i % 4 >= 2).collect::<Vec<_>>();
+        let mut clf = LGBMClassifier::new(_params());
+        assert!(clf.predict(matrix).is_err());
+        clf.fit(matrix, &label).unwrap();
+        assert_eq!(clf.classes(), &[false, true]);
+        assert_eq!(clf.booster().unwrap().num_class(), Ok(1));
+
+        let proba = clf.predict_proba(matrix).unwrap();
+        assert_eq!(proba.len(), 40);
+        assert!(proba.iter().all(|p| (p[0] + p[1] - 1.0).abs() < 1e-9));
+        assert_eq!(clf.predict(matrix).unwrap(), label);
+
+        assert!(clf.fit(matrix, &[true; 40]).is_err());
+    }
+
+    #[test]
+    fn multiclass_classifier() {
+        let data = _data();
+        let matrix = MatrixView::new(&data, 40, 2, MatrixLayout::RowMajor).unwrap();
+        let names = ["c", "a", "d", "b"];
+        let label = (0..40)
+            .map(|i| names[i % 4].to_string())
+            .collect::<Vec<_>>();
+        let mut clf = LGBMClassifier::new(_params());
+        clf.fit(matrix, &label).unwrap();
+        assert_eq!(clf.classes(), &["a", "b", "c", "d"]);
+        assert_eq!(clf.booster().unwrap().num_class(), Ok(4));
+        assert_eq!(clf.predict_proba(matrix).unwrap()[0].len(), 4);
+        assert_eq!(clf.predict(matrix).unwrap(), label);
+
+        let filename = "./test/test_classifier.output";
+        clf.save_file(filename).unwrap();
+        let loaded = LGBMClassifier::<String>::from_file(filename).unwrap();
+        assert_eq!(loaded.classes(), clf.classes());
+        assert_eq!(loaded.predict(matrix).unwrap(), label);
+        assert!(LGBMClassifier::<i64>::from_file(filename).is_err());
+        let _ = fs::remove_file(filename);
+
+        let mut clf = LGBMClassifier::new(json!({"num_class": 3}));
+        assert!(clf.fit(matrix, &label).is_err());
+    }
+
+    #[test]
+    fn regressor() {
+        let data = _data();
+        let matrix = MatrixView::new(&data, 40, 2, MatrixLayout::RowMajor).unwrap();
+        let label = (0..40).map(|i| (i % 4) as f32).collect::<Vec<_>>();
+        let mut reg = LGBMRegressor::new(_params());
+        reg.fit(matrix, &label).unwrap();
+        let predicted = reg.predict(matrix).unwrap();
+        assert_eq!(predicted.len(), 40);
+        assert!(predicted[3] > predicted[0]);
+
+        let filename = "./test/test_regressor.output";
+        reg.save_file(filename).unwrap();
+        let loaded = LGBMRegressor::from_file(filename).unwrap();
+        assert_eq!(loaded.predict(matrix).unwrap(), predicted);
+        let _ = fs::remove_file(filename);
+    }
+
+    #[test]
+    fn ranker() {
+        let data = _data();
+        let matrix = MatrixView::new(&data, 40, 2, MatrixLayout::RowMajor).unwrap();
+        let label = (0..40).map(|i| (i % 4) as f32).collect::<Vec<_>>();
+        let mut ranker = LGBMRanker::new(_params());
+        assert!(ranker.fit(matrix, &label, &[10, 10]).is_err());
+        ranker.fit(matrix, &label, &[8; 5]).unwrap();
+        let scores = ranker.predict(matrix).unwrap();
+        assert!(scores[3] > scores[0]);
+    }
+}
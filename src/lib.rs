@@ -1,3 +1,5 @@
+#[macro_use]
+extern crate derive_builder;
 extern crate libc;
 extern crate lightgbm_sys;
 extern crate serde_json;
@@ -5,18 +7,75 @@ extern crate serde_json;
 #[cfg(feature = "dataframe")]
 extern crate polars;
 
-#[macro_use]
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+
+#[cfg(feature = "arrow")]
+extern crate arrow;
+
+#[cfg(feature = "log")]
+extern crate log;
+
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
 macro_rules! lgbm_call {
-    ($x:expr) => {
-        Error::check_return_value(unsafe { $x })
-    };
+    (lightgbm_sys::$func:ident($($arg:expr),* $(,)*)) => {{
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        crate::logging::register_callback();
+        Error::check_return_value(
+            unsafe { lightgbm_sys::$func($($arg),*) },
+            stringify!($func),
+        )
+    }};
 }
 
 mod error;
 pub use error::{Error, Result};
 
+mod utils;
+
+#[cfg(any(feature = "log", feature = "tracing"))]
+mod logging;
+
+mod matrix;
+pub use matrix::{DType, MatrixLayout, MatrixView};
+
+mod parameters;
+pub use parameters::{
+    Column, DatasetParameters, DatasetParametersBuilder, PredictParameters,
+    PredictParametersBuilder, PredictType,
+};
+
+#[cfg(feature = "arrow")]
+mod record_batch;
+
+#[cfg(feature = "dataframe")]
+mod dataframe;
+
+mod categorical;
+pub use categorical::{CategoricalEncoder, CategoricalFeature, UnseenCategory};
+
 mod dataset;
 pub use dataset::Dataset;
 
+mod streaming;
+pub use streaming::DatasetStream;
+
+mod prediction;
+pub use prediction::Prediction;
+
 mod booster;
 pub use booster::Booster;
+
+mod cv;
+pub use cv::{cv, is_higher_better, CvParameters, CvParametersBuilder, CvResult, FoldStrategy};
+
+mod estimator;
+pub use estimator::{ClassLabel, LGBMClassifier, LGBMRanker, LGBMRegressor};
+
+mod search;
+pub use search::{
+    search, Distribution, Evaluation, ParameterSpace, SearchCandidate, SearchParameters,
+    SearchParametersBuilder, SearchResult, SearchStrategy,
+};
@@ -0,0 +1,138 @@
+//! Forwarding of LightGBM's log messages to the `log` or `tracing` crate.
+//!
+//! LightGBM keeps its log callback per thread, so the callback is registered on each thread
+//! before its first call into LightGBM. A message arrives in pieces, the `[LightGBM] [Level] `
+//! prefix, the text and the line break, and is buffered until the line is complete.
+//!
+//! Messages are filtered by LightGBM before they reach the callback, so the `verbosity`
+//! parameter of a `Dataset` or `Booster` still silences them: `"verbosity": -1` keeps only
+//! fatal errors.
+
+use std::cell::{Cell, RefCell};
+use std::ffi::CStr;
+use std::panic;
+
+use libc::c_char;
+use lightgbm_sys;
+
+/// Level of a LightGBM log message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Level {
+    Fatal,
+    Warning,
+    Info,
+    Debug,
+}
+
+thread_local! {
+    static REGISTERED: Cell<bool> = const { Cell::new(false) };
+    static BUFFER: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Register the callback on the current thread, once.
+pub(crate) fn register_callback() {
+    REGISTERED.with(|registered| {
+        if !registered.get() {
+            // Registration only stores the pointer and cannot fail.
+            unsafe { lightgbm_sys::LGBM_RegisterLogCallback(Some(log_callback)) };
+            registered.set(true);
+        }
+    });
+}
+
+unsafe extern "C" fn log_callback(msg: *const c_char) {
+    if msg.is_null() {
+        return;
+    }
+    let piece = CStr::from_ptr(msg).to_string_lossy();
+    // A panic must not unwind into LightGBM.
+    let _ = panic::catch_unwind(|| {
+        BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            buffer.push_str(&piece);
+            while let Some(end) = buffer.find('\n') {
+                let line: String = buffer.drain(..=end).collect();
+                emit(line.trim_end());
+            }
+        })
+    });
+}
+
+/// Split a line into its level and message, defaulting to `Info` without a known prefix.
+fn parse_line(line: &str) -> (Level, &str) {
+    let line = line.strip_prefix("[LightGBM] ").unwrap_or(line);
+    let prefixes = [
+        ("[Fatal] ", Level::Fatal),
+        ("[Warning] ", Level::Warning),
+        ("[Info] ", Level::Info),
+        ("[Debug] ", Level::Debug),
+    ];
+    for &(prefix, level) in &prefixes {
+        if let Some(message) = line.strip_prefix(prefix) {
+            return (level, message);
+        }
+    }
+    (Level::Info, line)
+}
+
+#[cfg(feature = "tracing")]
+fn emit(line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    match parse_line(line) {
+        (Level::Fatal, message) => tracing::error!(target: "lightgbm", "{}", message),
+        (Level::Warning, message) => tracing::warn!(target: "lightgbm", "{}", message),
+        (Level::Info, message) => tracing::info!(target: "lightgbm", "{}", message),
+        (Level::Debug, message) => tracing::debug!(target: "lightgbm", "{}", message),
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+fn emit(line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    let (level, message) = parse_line(line);
+    let level = match level {
+        Level::Fatal => log::Level::Error,
+        Level::Warning => log::Level::Warn,
+        Level::Info => log::Level::Info,
+        Level::Debug => log::Level::Debug,
+    };
+    log::log!(target: "lightgbm", level, "{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_line("[LightGBM] [Warning] No further splits with positive gain"),
+            (Level::Warning, "No further splits with positive gain")
+        );
+        assert_eq!(
+            parse_line("[LightGBM] [Info] Total Bins 6132"),
+            (Level::Info, "Total Bins 6132")
+        );
+        assert_eq!(parse_line("[LightGBM] [Debug] x"), (Level::Debug, "x"));
+        assert_eq!(parse_line("[LightGBM] [Fatal] x"), (Level::Fatal, "x"));
+        assert_eq!(parse_line("plain text"), (Level::Info, "plain text"));
+    }
+
+    #[test]
+    fn buffered_pieces() {
+        register_callback();
+        REGISTERED.with(|registered| assert!(registered.get()));
+        for piece in &["[LightGBM] [Info] ", "Total Bins 6132", "\n"] {
+            let piece = std::ffi::CString::new(*piece).unwrap();
+            unsafe { log_callback(piece.as_ptr()) };
+        }
+        BUFFER.with(|buffer| assert!(buffer.borrow().is_empty()));
+        let piece = std::ffi::CString::new("[LightGBM] [Info] partial").unwrap();
+        unsafe { log_callback(piece.as_ptr()) };
+        BUFFER.with(|buffer| assert_eq!(*buffer.borrow(), "[LightGBM] [Info] partial"));
+    }
+}
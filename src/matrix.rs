@@ -0,0 +1,264 @@
+//! Borrowed dense matrices passed to LightGBM without copying.
+
+use lightgbm_sys;
+use std::borrow::Cow;
+
+#[cfg(feature = "ndarray")]
+use ndarray::ArrayView2;
+
+use crate::{Error, Result};
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Element types LightGBM accepts for dense matrices: `f32` and `f64`.
+pub trait DType: private::Sealed + Copy + Send + Sync {
+    /// LightGBM's `C_API_DTYPE_*` constant of the type.
+    #[doc(hidden)]
+    const C_API_DTYPE: i32;
+
+    #[doc(hidden)]
+    fn to_f64(self) -> f64;
+}
+
+impl DType for f32 {
+    const C_API_DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT32 as i32;
+
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl DType for f64 {
+    const C_API_DTYPE: i32 = lightgbm_sys::C_API_DTYPE_FLOAT64 as i32;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Memory layout of a dense matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixLayout {
+    /// Values of a row are contiguous.
+    RowMajor,
+    /// Values of a column are contiguous.
+    ColumnMajor,
+}
+
+/// A dense matrix borrowed from a contiguous slice.
+///
+/// The slice is handed to LightGBM as is, without copying.
+///
+/// Example
+/// ```
+/// use lightgbm::{MatrixLayout, MatrixView};
+///
+/// let data = vec![1.0_f32, 0.1, 0.2,
+///                 0.7, 0.4, 0.5];
+/// let matrix = MatrixView::new(&data, 2, 3, MatrixLayout::RowMajor).unwrap();
+/// assert!(MatrixView::new(&data, 3, 3, MatrixLayout::RowMajor).is_err());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixView<'a, T: DType> {
+    data: &'a [T],
+    num_rows: usize,
+    num_cols: usize,
+    layout: MatrixLayout,
+}
+
+impl<'a, T: DType> MatrixView<'a, T> {
+    /// Wrap `data` holding `num_rows * num_cols` values in the given layout.
+    pub fn new(
+        data: &'a [T],
+        num_rows: usize,
+        num_cols: usize,
+        layout: MatrixLayout,
+    ) -> Result<Self> {
+        if num_rows.checked_mul(num_cols) != Some(data.len()) {
+            return Err(Error::invalid_input(format!(
+                "a {} x {} matrix needs {} values, got {}",
+                num_rows,
+                num_cols,
+                num_rows.saturating_mul(num_cols),
+                data.len()
+            )));
+        }
+        if num_rows > i32::MAX as usize || num_cols > i32::MAX as usize {
+            return Err(Error::invalid_input(format!(
+                "a {} x {} matrix is too large for LightGBM",
+                num_rows, num_cols
+            )));
+        }
+        Ok(Self {
+            data,
+            num_rows,
+            num_cols,
+            layout,
+        })
+    }
+
+    /// Number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Number of columns.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Memory layout.
+    pub fn layout(&self) -> MatrixLayout {
+        self.layout
+    }
+
+    pub(crate) fn as_slice(&self) -> &'a [T] {
+        self.data
+    }
+
+    /// The values in row-major order, copied only for a column-major matrix.
+    pub(crate) fn row_major_values(&self) -> Cow<'a, [T]> {
+        match self.layout {
+            MatrixLayout::RowMajor => Cow::Borrowed(self.data),
+            MatrixLayout::ColumnMajor => Cow::Owned(
+                (0..self.num_rows)
+                    .flat_map(|row| {
+                        (0..self.num_cols).map(move |col| self.data[col * self.num_rows + row])
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    pub(crate) fn get(&self, row: usize, col: usize) -> f64 {
+        match self.layout {
+            MatrixLayout::RowMajor => self.data[row * self.num_cols + col],
+            MatrixLayout::ColumnMajor => self.data[col * self.num_rows + row],
+        }
+        .to_f64()
+    }
+
+    /// Check that the values of the categorical columns are non-negative integers or `NaN`.
+    pub(crate) fn check_categorical(&self, categorical_idx: &[usize]) -> Result<()> {
+        for row_idx in 0..self.num_rows {
+            for &col in categorical_idx {
+                check_categorical_value(self.get(row_idx, col), row_idx, col)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_row_major(&self) -> i32 {
+        match self.layout {
+            MatrixLayout::RowMajor => 1,
+            MatrixLayout::ColumnMajor => 0,
+        }
+    }
+}
+
+/// Check that a value of a categorical column is a non-negative integer or `NaN`.
+pub(crate) fn check_categorical_value(value: f64, row_idx: usize, col: usize) -> Result<()> {
+    if !value.is_nan() && (value < 0.0 || value.fract() != 0.0) {
+        return Err(Error::invalid_input(format!(
+            "categorical value {} in row {}, column {} is not a non-negative integer",
+            value, row_idx, col
+        )));
+    }
+    Ok(())
+}
+
+/// Flatten rows of equal length into a row-major buffer, returning it with its shape.
+pub(crate) fn flatten_rows(data: Vec<Vec<f64>>) -> Result<(Vec<f64>, usize, usize)> {
+    let num_cols = data.first().map_or(0, |row| row.len());
+    if let Some(row_idx) = data.iter().position(|row| row.len() != num_cols) {
+        return Err(Error::invalid_input(format!(
+            "row {} has {} values, expected {}",
+            row_idx,
+            data[row_idx].len(),
+            num_cols
+        )));
+    }
+    let num_rows = data.len();
+    Ok((data.into_iter().flatten().collect(), num_rows, num_cols))
+}
+
+/// Call `f` with a [`MatrixView`] of an ndarray view, borrowing its memory when it is
+/// contiguous in C or Fortran order and copying it to C order otherwise.
+#[cfg(feature = "ndarray")]
+pub(crate) fn with_array_view<T, R, F>(data: &ArrayView2<T>, f: F) -> Result<R>
+where
+    T: DType,
+    F: FnOnce(MatrixView<T>) -> Result<R>,
+{
+    let (num_rows, num_cols) = data.dim();
+    if let Some(slice) = data.as_slice() {
+        return f(MatrixView::new(
+            slice,
+            num_rows,
+            num_cols,
+            MatrixLayout::RowMajor,
+        )?);
+    }
+    let transposed = data.t();
+    if let Some(slice) = transposed.as_slice() {
+        return f(MatrixView::new(
+            slice,
+            num_rows,
+            num_cols,
+            MatrixLayout::ColumnMajor,
+        )?);
+    }
+    let standard = data.as_standard_layout();
+    let slice = standard
+        .as_slice()
+        .ok_or_else(|| Error::invalid_input("cannot convert array to standard layout"))?;
+    f(MatrixView::new(
+        slice,
+        num_rows,
+        num_cols,
+        MatrixLayout::RowMajor,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        let data = vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let row_major = MatrixView::new(&data, 2, 3, MatrixLayout::RowMajor).unwrap();
+        assert_eq!(row_major.get(1, 0), 4.0);
+        let col_major = MatrixView::new(&data, 2, 3, MatrixLayout::ColumnMajor).unwrap();
+        assert_eq!(col_major.get(1, 0), 2.0);
+        assert_eq!(&*row_major.row_major_values(), &data[..]);
+        assert_eq!(
+            &*col_major.row_major_values(),
+            &[1.0, 3.0, 5.0, 2.0, 4.0, 6.0]
+        );
+        assert!(MatrixView::new(&data, 4, 2, MatrixLayout::RowMajor).is_err());
+    }
+
+    #[test]
+    fn categorical_values() {
+        let data = vec![0.0, 1.5, 2.0, f64::NAN, -1.0, 3.0];
+        let matrix = MatrixView::new(&data, 2, 3, MatrixLayout::RowMajor).unwrap();
+        assert!(matrix.check_categorical(&[0]).is_ok());
+        assert!(matrix.check_categorical(&[1]).is_err());
+        assert!(matrix.check_categorical(&[2]).is_ok());
+        assert!(matrix.check_categorical(&[0, 2]).is_ok());
+        let matrix = MatrixView::new(&data, 3, 2, MatrixLayout::RowMajor).unwrap();
+        assert!(matrix.check_categorical(&[0]).is_err());
+    }
+
+    #[test]
+    fn flatten() {
+        let data = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+        assert_eq!(flatten_rows(data), Ok((vec![1.0, 2.0, 3.0, 4.0], 2, 2)));
+        assert!(flatten_rows(vec![vec![1.0, 2.0], vec![3.0]]).is_err());
+    }
+}
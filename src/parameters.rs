@@ -0,0 +1,531 @@
+//! Typed parameters passed to LightGBM.
+
+use lightgbm_sys;
+
+use crate::categorical::resolve_categorical_feature;
+use crate::{Error, Result};
+
+/// A column of the input data, referenced either by index or by name.
+///
+/// Example
+/// ```
+/// use lightgbm::Column;
+///
+/// let by_index = Column::from(0);
+/// let by_name = Column::from("target");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Zero-based column index.
+    Index(usize),
+    /// Column name.
+    Name(String),
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Column::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for Column {
+    fn from(name: &'a str) -> Self {
+        Column::Name(name.to_string())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        Column::Name(name)
+    }
+}
+
+/// Type of the values computed by a prediction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PredictType {
+    /// Transformed scores, e.g. probabilities for classification; `num_class` values per row.
+    Normal,
+    /// Raw scores before the objective's transformation; `num_class` values per row.
+    RawScore,
+    /// Index of the predicted leaf in every tree; one value per tree.
+    LeafIndex,
+    /// Feature contributions (SHAP values) plus the expected value;
+    /// `num_class * (num_feature + 1)` values per row.
+    Contrib,
+}
+
+impl PredictType {
+    pub(crate) fn to_c_api(self) -> i32 {
+        (match self {
+            PredictType::Normal => lightgbm_sys::C_API_PREDICT_NORMAL,
+            PredictType::RawScore => lightgbm_sys::C_API_PREDICT_RAW_SCORE,
+            PredictType::LeafIndex => lightgbm_sys::C_API_PREDICT_LEAF_INDEX,
+            PredictType::Contrib => lightgbm_sys::C_API_PREDICT_CONTRIB,
+        }) as i32
+    }
+}
+
+/// Parameters used when constructing a [`Dataset`](crate::Dataset).
+///
+/// Every value defaults to LightGBM's default. `header`, `label_column` and `ignore_column`
+/// only apply when loading from a file; as in LightGBM, integer indices of `ignore_column`
+/// and `categorical_feature` do not count the label column of a file.
+/// Parameters are validated before they are passed to LightGBM.
+///
+/// Example
+/// ```
+/// use lightgbm::{Column, Dataset, DatasetParametersBuilder};
+///
+/// let params = DatasetParametersBuilder::default()
+///     .max_bin(63)
+///     .feature_names(vec!["age".to_string(), "color".to_string()])
+///     .categorical_feature(vec![Column::from("color")])
+///     .build()
+///     .unwrap();
+///
+/// let data = vec![vec![1.0, 2.0],
+///                vec![0.7, 0.0],
+///                vec![0.1, 1.0]];
+/// let label = vec![0.0, 0.0, 1.0];
+/// let dataset = Dataset::from_mat_with_params(data, label, &params, None).unwrap();
+/// ```
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct DatasetParameters {
+    /// Max number of bins that feature values will be bucketed in.
+    #[builder(default = "255")]
+    max_bin: i32,
+    /// Minimal number of data inside one bin.
+    #[builder(default = "3")]
+    min_data_in_bin: i32,
+    /// Number of data sampled to construct feature discretization bins.
+    #[builder(default = "200000")]
+    bin_construct_sample_cnt: i32,
+    /// Whether the input file has a header line.
+    #[builder(default = "false")]
+    header: bool,
+    /// Label column of the input file, the first column by default.
+    #[builder(default = "None", setter(into))]
+    label_column: Option<Column>,
+    /// Columns of the input file to ignore.
+    #[builder(default = "Vec::new()")]
+    ignore_column: Vec<Column>,
+    /// Features to treat as categorical.
+    #[builder(default = "Vec::new()")]
+    categorical_feature: Vec<Column>,
+    /// Whether to handle missing values; when `false`, they are treated as zero.
+    #[builder(default = "true")]
+    use_missing: bool,
+    /// Whether to treat zero values as missing.
+    #[builder(default = "false")]
+    zero_as_missing: bool,
+    /// Feature names, instead of LightGBM's `Column_0`, `Column_1`, ...
+    #[builder(default = "Vec::new()")]
+    feature_names: Vec<String>,
+}
+
+impl Default for DatasetParameters {
+    fn default() -> Self {
+        DatasetParametersBuilder::default().build().unwrap()
+    }
+}
+
+impl DatasetParameters {
+    pub(crate) fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    pub(crate) fn bin_construct_sample_cnt(&self) -> usize {
+        self.bin_construct_sample_cnt.max(0) as usize
+    }
+
+    /// Copy the parameters for a table that names its columns, declaring the columns at
+    /// `categorical_idx` categorical in addition to the configured categorical features.
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    pub(crate) fn with_table_columns(
+        &self,
+        feature_names: Vec<String>,
+        categorical_idx: &[usize],
+    ) -> Result<Self> {
+        if !self.feature_names.is_empty() {
+            return Err(Error::invalid_parameter(
+                "feature_names cannot be set, the column names of the table are used",
+            ));
+        }
+        let mut params = self.clone();
+        params.feature_names = feature_names;
+        params
+            .categorical_feature
+            .extend(categorical_idx.iter().map(|&i| Column::Index(i)));
+        Ok(params)
+    }
+
+    /// Validate the parameters and build the parameter string for an in-memory matrix with
+    /// `num_feature` columns.
+    ///
+    /// Also returns the resolved indices of the categorical features.
+    pub(crate) fn to_mat_string(&self, num_feature: usize) -> Result<(String, Vec<usize>)> {
+        if self.header || self.label_column.is_some() || !self.ignore_column.is_empty() {
+            return Err(Error::invalid_parameter(
+                "header, label_column and ignore_column are only supported when loading from file",
+            ));
+        }
+        if !self.feature_names.is_empty() && self.feature_names.len() != num_feature {
+            return Err(Error::invalid_parameter(format!(
+                "got {} feature names for {} features",
+                self.feature_names.len(),
+                num_feature
+            )));
+        }
+
+        let categorical_idx = resolve_categorical_feature(
+            &self.categorical_feature,
+            &self.feature_names,
+            num_feature,
+        )?;
+        let mut params = self.binning_params()?;
+        if !categorical_idx.is_empty() {
+            params.push(format!(
+                "categorical_feature={}",
+                categorical_idx
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+        Ok((params.join(" "), categorical_idx))
+    }
+
+    /// Validate the parameters and build the parameter string for loading a file.
+    pub(crate) fn to_file_string(&self) -> Result<String> {
+        let mut params = self.binning_params()?;
+        params.push(format!("header={}", self.header));
+        if let Some(ref column) = self.label_column {
+            params.push(format!(
+                "label_column={}",
+                self.columns_param(std::slice::from_ref(column))?
+            ));
+        }
+        if !self.ignore_column.is_empty() {
+            params.push(format!(
+                "ignore_column={}",
+                self.columns_param(&self.ignore_column)?
+            ));
+        }
+        if !self.categorical_feature.is_empty() {
+            params.push(format!(
+                "categorical_feature={}",
+                self.columns_param(&self.categorical_feature)?
+            ));
+        }
+        Ok(params.join(" "))
+    }
+
+    fn binning_params(&self) -> Result<Vec<String>> {
+        if self.max_bin <= 1 {
+            return Err(Error::invalid_parameter(format!(
+                "max_bin must be greater than 1, got {}",
+                self.max_bin
+            )));
+        }
+        if self.min_data_in_bin <= 0 {
+            return Err(Error::invalid_parameter(format!(
+                "min_data_in_bin must be positive, got {}",
+                self.min_data_in_bin
+            )));
+        }
+        if self.bin_construct_sample_cnt <= 0 {
+            return Err(Error::invalid_parameter(format!(
+                "bin_construct_sample_cnt must be positive, got {}",
+                self.bin_construct_sample_cnt
+            )));
+        }
+        Ok(vec![
+            format!("max_bin={}", self.max_bin),
+            format!("min_data_in_bin={}", self.min_data_in_bin),
+            format!("bin_construct_sample_cnt={}", self.bin_construct_sample_cnt),
+            format!("use_missing={}", self.use_missing),
+            format!("zero_as_missing={}", self.zero_as_missing),
+        ])
+    }
+
+    /// Format columns as LightGBM expects in a file parameter: `0,1,2` or `name:a,b,c`.
+    fn columns_param(&self, columns: &[Column]) -> Result<String> {
+        if columns.iter().all(|c| matches!(c, Column::Index(_))) {
+            return Ok(columns
+                .iter()
+                .map(|c| match c {
+                    Column::Index(i) => i.to_string(),
+                    Column::Name(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+                .join(","));
+        }
+
+        let names = columns
+            .iter()
+            .map(|c| match c {
+                Column::Name(name) => Ok(name.as_str()),
+                Column::Index(_) => Err(Error::invalid_parameter(
+                    "columns of a file parameter cannot mix indices and names",
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !self.header {
+            return Err(Error::invalid_parameter(
+                "columns can only be referenced by name when the file has a header",
+            ));
+        }
+        if let Some(name) = names.iter().find(|n| {
+            n.is_empty() || n.contains(|c: char| c.is_whitespace() || c == ',' || c == '=')
+        }) {
+            return Err(Error::invalid_parameter(format!(
+                "invalid column name '{}'",
+                name
+            )));
+        }
+        Ok(format!("name:{}", names.join(",")))
+    }
+}
+
+/// Parameters used when predicting with a [`Booster`](crate::Booster).
+///
+/// Every value defaults to LightGBM's default. Early stopping only applies to binary and
+/// multiclass models: the trees are evaluated by blocks of `pred_early_stop_freq` and a row
+/// stops as soon as its margin exceeds `pred_early_stop_margin`.
+///
+/// Example
+/// ```
+/// use lightgbm::{Booster, MatrixLayout, MatrixView, PredictParametersBuilder, PredictType};
+///
+/// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+/// let params = PredictParametersBuilder::default()
+///     .predict_type(PredictType::RawScore)
+///     .pred_early_stop(true)
+///     .pred_early_stop_margin(1.5)
+///     .num_threads(2)
+///     .build()
+///     .unwrap();
+/// let data = vec![0.5_f64; 28 * 2];
+/// let matrix = MatrixView::new(&data, 2, 28, MatrixLayout::RowMajor).unwrap();
+/// let prediction = bst.predict_with_params(matrix, &params).unwrap();
+/// ```
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct PredictParameters {
+    /// Type of the predicted values.
+    #[builder(default = "PredictType::Normal")]
+    predict_type: PredictType,
+    /// First boosting iteration used.
+    #[builder(default = "0")]
+    start_iteration: i32,
+    /// Number of boosting iterations used from `start_iteration`, all of them when not positive.
+    #[builder(default = "-1")]
+    num_iteration: i32,
+    /// Whether to stop evaluating trees early for rows with a large enough margin.
+    #[builder(default = "false")]
+    pred_early_stop: bool,
+    /// Number of iterations between early stopping checks.
+    #[builder(default = "10")]
+    pred_early_stop_freq: i32,
+    /// Margin above which a row stops early.
+    #[builder(default = "10.0")]
+    pred_early_stop_margin: f64,
+    /// Whether to skip checking that the data has as many features as the model.
+    #[builder(default = "false")]
+    predict_disable_shape_check: bool,
+    /// Number of OpenMP threads of LightGBM, its default (all cores) when 0.
+    #[builder(default = "0")]
+    num_threads: usize,
+}
+
+impl Default for PredictParameters {
+    fn default() -> Self {
+        PredictParametersBuilder::default().build().unwrap()
+    }
+}
+
+impl From<PredictType> for PredictParameters {
+    fn from(predict_type: PredictType) -> Self {
+        PredictParametersBuilder::default()
+            .predict_type(predict_type)
+            .build()
+            .unwrap()
+    }
+}
+
+impl PredictParameters {
+    pub(crate) fn predict_type(&self) -> PredictType {
+        self.predict_type
+    }
+
+    pub(crate) fn start_iteration(&self) -> i32 {
+        self.start_iteration
+    }
+
+    pub(crate) fn num_iteration(&self) -> i32 {
+        self.num_iteration
+    }
+
+    /// Validate the parameters and build the parameter string for prediction.
+    pub(crate) fn to_predict_string(&self) -> Result<String> {
+        if self.start_iteration < 0 {
+            return Err(Error::invalid_parameter(format!(
+                "start_iteration must not be negative, got {}",
+                self.start_iteration
+            )));
+        }
+        if self.pred_early_stop_freq <= 0 {
+            return Err(Error::invalid_parameter(format!(
+                "pred_early_stop_freq must be positive, got {}",
+                self.pred_early_stop_freq
+            )));
+        }
+        if !(self.pred_early_stop_margin >= 0.0 && self.pred_early_stop_margin.is_finite()) {
+            return Err(Error::invalid_parameter(format!(
+                "pred_early_stop_margin must be a non-negative number, got {}",
+                self.pred_early_stop_margin
+            )));
+        }
+        let mut params = vec![
+            format!("pred_early_stop={}", self.pred_early_stop),
+            format!("pred_early_stop_freq={}", self.pred_early_stop_freq),
+            format!("pred_early_stop_margin={}", self.pred_early_stop_margin),
+            format!(
+                "predict_disable_shape_check={}",
+                self.predict_disable_shape_check
+            ),
+        ];
+        if self.num_threads > 0 {
+            params.push(format!("num_threads={}", self.num_threads));
+        }
+        Ok(params.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn predict_params() {
+        assert_eq!(
+            PredictParameters::default().to_predict_string(),
+            Ok(String::from(
+                "pred_early_stop=false pred_early_stop_freq=10 pred_early_stop_margin=10 \
+                 predict_disable_shape_check=false"
+            ))
+        );
+        let params = PredictParametersBuilder::default()
+            .pred_early_stop(true)
+            .pred_early_stop_margin(0.5)
+            .num_threads(4)
+            .build()
+            .unwrap();
+        assert_eq!(
+            params.to_predict_string(),
+            Ok(String::from(
+                "pred_early_stop=true pred_early_stop_freq=10 pred_early_stop_margin=0.5 \
+                 predict_disable_shape_check=false num_threads=4"
+            ))
+        );
+        let params = PredictParametersBuilder::default()
+            .pred_early_stop_freq(0)
+            .build()
+            .unwrap();
+        assert!(params.to_predict_string().is_err());
+        let params = PredictParameters::from(PredictType::LeafIndex);
+        assert_eq!(params.predict_type(), PredictType::LeafIndex);
+    }
+
+    #[test]
+    fn default_params() {
+        let params = DatasetParameters::default();
+        assert_eq!(
+            params.to_file_string(),
+            Ok(String::from(
+                "max_bin=255 min_data_in_bin=3 bin_construct_sample_cnt=200000 \
+                 use_missing=true zero_as_missing=false header=false"
+            ))
+        );
+    }
+
+    #[test]
+    fn file_params() {
+        let params = DatasetParametersBuilder::default()
+            .header(true)
+            .label_column(Column::from("target"))
+            .ignore_column(vec![Column::from("id"), Column::from("date")])
+            .categorical_feature(vec![Column::from("color")])
+            .build()
+            .unwrap();
+        let param_string = params.to_file_string().unwrap();
+        assert!(param_string.contains("label_column=name:target"));
+        assert!(param_string.contains("ignore_column=name:id,date"));
+        assert!(param_string.contains("categorical_feature=name:color"));
+
+        let params = DatasetParametersBuilder::default()
+            .label_column(Column::from("target"))
+            .build()
+            .unwrap();
+        assert!(params.to_file_string().is_err());
+
+        let params = DatasetParametersBuilder::default()
+            .header(true)
+            .ignore_column(vec![Column::from(0), Column::from("id")])
+            .build()
+            .unwrap();
+        assert!(params.to_file_string().is_err());
+    }
+
+    #[test]
+    fn mat_params() {
+        let params = DatasetParametersBuilder::default()
+            .max_bin(15)
+            .feature_names(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .categorical_feature(vec![Column::from("c"), Column::from(0)])
+            .build()
+            .unwrap();
+        let (param_string, categorical_idx) = params.to_mat_string(3).unwrap();
+        assert!(param_string.contains("max_bin=15"));
+        assert!(param_string.contains("categorical_feature=0,2"));
+        assert_eq!(categorical_idx, vec![0, 2]);
+        assert!(params.to_mat_string(2).is_err());
+
+        let params = DatasetParametersBuilder::default()
+            .header(true)
+            .build()
+            .unwrap();
+        assert!(params.to_mat_string(3).is_err());
+    }
+
+    #[cfg(any(feature = "arrow", feature = "dataframe"))]
+    #[test]
+    fn table_columns() {
+        let params = DatasetParametersBuilder::default()
+            .categorical_feature(vec![Column::from("a")])
+            .build()
+            .unwrap();
+        let params = params
+            .with_table_columns(
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                &[2],
+            )
+            .unwrap();
+        assert_eq!(params.to_mat_string(3).unwrap().1, vec![0, 2]);
+        assert!(params.with_table_columns(vec![], &[]).is_err());
+    }
+
+    #[test]
+    fn invalid_binning() {
+        let params = DatasetParametersBuilder::default()
+            .max_bin(1)
+            .build()
+            .unwrap();
+        assert!(params.to_file_string().is_err());
+        let params = DatasetParametersBuilder::default()
+            .min_data_in_bin(0)
+            .build()
+            .unwrap();
+        assert!(params.to_mat_string(1).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! Prediction results with an explicit shape.
+
+/// Predicted values, `num_outputs` for each of `num_rows` rows, flat in row-major order.
+///
+/// The shape is the same for every objective: a regression or binary model has one output per
+/// row, a `multiclass` or `multiclassova` model one output per class.
+///
+/// Example
+/// ```
+/// use lightgbm::Booster;
+///
+/// let bst = Booster::from_file(&"./test/test_from_file.input").unwrap();
+/// let prediction = bst.predict_mat(vec![vec![0.5; 28], vec![0.1; 28]]).unwrap();
+/// assert_eq!(prediction.num_rows(), 2);
+/// assert_eq!(prediction.num_outputs(), 1);
+/// for row in prediction.rows() {
+///     println!("{:?}", row);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prediction {
+    values: Vec<f64>,
+    num_rows: usize,
+    num_outputs: usize,
+}
+
+impl Prediction {
+    /// Wrap the flat output of LightGBM for `num_rows` rows.
+    pub(crate) fn new(values: Vec<f64>, num_rows: usize) -> Self {
+        let num_outputs = values.len().checked_div(num_rows).unwrap_or(0);
+        Prediction {
+            values,
+            num_rows,
+            num_outputs,
+        }
+    }
+
+    /// Number of predicted rows.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Number of values per row.
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+
+    /// Values of the given row.
+    ///
+    /// Panics if `row` is out of range.
+    pub fn row(&self, row: usize) -> &[f64] {
+        assert!(row < self.num_rows, "row {} is out of range", row);
+        &self.values[row * self.num_outputs..(row + 1) * self.num_outputs]
+    }
+
+    /// Iterate over the values of each row.
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.num_rows).map(move |row| self.row(row))
+    }
+
+    /// Values of the given output for every row, such as the probabilities of one class.
+    ///
+    /// Panics if `output` is out of range.
+    pub fn column(&self, output: usize) -> Vec<f64> {
+        assert!(
+            output < self.num_outputs,
+            "output {} is out of range",
+            output
+        );
+        self.values
+            .iter()
+            .skip(output)
+            .step_by(self.num_outputs)
+            .copied()
+            .collect()
+    }
+
+    /// All values, flat in row-major order.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Take the values, flat in row-major order.
+    pub fn into_vec(self) -> Vec<f64> {
+        self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape() {
+        let prediction = Prediction::new(vec![0.1, 0.9, 0.3, 0.7, 0.5, 0.5], 3);
+        assert_eq!(prediction.num_rows(), 3);
+        assert_eq!(prediction.num_outputs(), 2);
+        assert_eq!(prediction.row(1), &[0.3, 0.7]);
+        assert_eq!(prediction.rows().count(), 3);
+        assert_eq!(prediction.column(1), vec![0.9, 0.7, 0.5]);
+
+        let prediction = Prediction::new(vec![0.1, 0.9], 2);
+        assert_eq!(prediction.num_outputs(), 1);
+        assert_eq!(prediction.rows().collect::<Vec<_>>(), vec![&[0.1], &[0.9]]);
+        assert_eq!(prediction.into_vec(), vec![0.1, 0.9]);
+
+        let empty = Prediction::new(Vec::new(), 0);
+        assert_eq!(empty.rows().count(), 0);
+    }
+}
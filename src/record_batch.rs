@@ -0,0 +1,237 @@
+//! Conversion of Arrow record batches to the dense matrices passed to LightGBM.
+//!
+//! The bundled LightGBM has no Arrow C API, so columns are copied into a column-major buffer.
+
+use std::collections::HashSet;
+
+use arrow::array::{ArrayRef, AsArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float32Type, Float64Type, Int64Type};
+use arrow::record_batch::RecordBatch;
+
+use crate::utils::group_sizes_from_ids;
+use crate::{CategoricalEncoder, Error, Result};
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| Error::invalid_input(format!("record batch has no column '{}'", name)))
+}
+
+fn cast_column(column: &ArrayRef, name: &str, data_type: &DataType) -> Result<ArrayRef> {
+    cast(column, data_type)
+        .map_err(|e| Error::invalid_input(format!("cannot convert column '{}': {}", name, e)))
+}
+
+fn is_number(data_type: &DataType) -> bool {
+    data_type.is_numeric() || *data_type == DataType::Boolean
+}
+
+/// Read a dictionary column as the string value of every row, with nulls of the keys or of the
+/// dictionary values as `None`.
+fn dictionary_values(array: &ArrayRef, name: &str) -> Result<ArrayRef> {
+    cast_column(array, name, &DataType::Utf8)
+}
+
+/// Learn the categories of the dictionary columns among `columns`, in order of first appearance.
+///
+/// Categories are kept by value, as the dictionary keys of a column depend on the batch it was
+/// built in.
+pub(crate) fn fit_categories<S: AsRef<str>>(
+    batch: &RecordBatch,
+    columns: &[S],
+) -> Result<CategoricalEncoder> {
+    let mut categorical_feature = Vec::new();
+    let mut categories = Vec::new();
+    for (col_idx, name) in columns.iter().enumerate() {
+        let name = name.as_ref();
+        let array = column(batch, name)?;
+        if array.as_any_dictionary_opt().is_some() {
+            let values = dictionary_values(array, name)?;
+            let mut seen = HashSet::new();
+            categories.push(
+                values
+                    .as_string::<i32>()
+                    .iter()
+                    .flatten()
+                    .filter(|value| seen.insert(*value))
+                    .map(String::from)
+                    .collect(),
+            );
+            categorical_feature.push(col_idx);
+        }
+    }
+    Ok(CategoricalEncoder::from_categories(
+        categorical_feature,
+        categories,
+    ))
+}
+
+/// Copy the given columns into a column-major matrix.
+///
+/// Numeric and boolean columns are converted to `f64` with nulls as `NaN`. Dictionary columns
+/// are encoded by value with `encoder`, and must be its categorical features.
+pub(crate) fn feature_matrix<S: AsRef<str>>(
+    batch: &RecordBatch,
+    columns: &[S],
+    encoder: Option<&CategoricalEncoder>,
+) -> Result<Vec<f64>> {
+    let mut data = Vec::with_capacity(batch.num_rows() * columns.len());
+    for (col_idx, name) in columns.iter().enumerate() {
+        let name = name.as_ref();
+        let array = column(batch, name)?;
+        let encoder = encoder.filter(|encoder| encoder.is_categorical(col_idx));
+        if array.as_any_dictionary_opt().is_some() {
+            let encoder = encoder.ok_or_else(|| {
+                Error::invalid_input(format!(
+                    "column '{}' of type {} is not a known categorical feature",
+                    name,
+                    array.data_type()
+                ))
+            })?;
+            let values = dictionary_values(array, name)?;
+            data.extend(encoder.encode_column(col_idx, values.as_string::<i32>().iter())?);
+        } else if encoder.is_some() {
+            return Err(Error::invalid_input(format!(
+                "categorical feature '{}' has type {} instead of a dictionary",
+                name,
+                array.data_type()
+            )));
+        } else if is_number(array.data_type()) {
+            let values = cast_column(array, name, &DataType::Float64)?;
+            data.extend(
+                values
+                    .as_primitive::<Float64Type>()
+                    .iter()
+                    .map(|v| v.unwrap_or(f64::NAN)),
+            );
+        } else {
+            return Err(Error::invalid_input(format!(
+                "column '{}' of type {} is neither numeric nor dictionary-encoded",
+                name,
+                array.data_type()
+            )));
+        }
+    }
+    Ok(data)
+}
+
+/// Read a numeric column without nulls, such as the label or the weights, as `f32`.
+pub(crate) fn f32_column(batch: &RecordBatch, name: &str) -> Result<Vec<f32>> {
+    let array = column(batch, name)?;
+    if !is_number(array.data_type()) {
+        return Err(Error::invalid_input(format!(
+            "column '{}' of type {} is not numeric",
+            name,
+            array.data_type()
+        )));
+    }
+    cast_column(array, name, &DataType::Float32)?
+        .as_primitive::<Float32Type>()
+        .iter()
+        .enumerate()
+        .map(|(row_idx, v)| {
+            v.ok_or_else(|| {
+                Error::invalid_input(format!("null in row {} of column '{}'", row_idx, name))
+            })
+        })
+        .collect()
+}
+
+/// Read an integer column of query ids as the sizes of consecutive groups.
+pub(crate) fn group_sizes(batch: &RecordBatch, name: &str) -> Result<Vec<i32>> {
+    let array = column(batch, name)?;
+    if !array.data_type().is_integer() {
+        return Err(Error::invalid_input(format!(
+            "group column '{}' of type {} is not an integer column",
+            name,
+            array.data_type()
+        )));
+    }
+    let ids = cast_column(array, name, &DataType::Int64)?;
+    group_sizes_from_ids(ids.as_primitive::<Int64Type>().iter(), name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{DictionaryArray, Float32Array, Int32Array, Int8Array, StringArray};
+    use arrow::datatypes::Int8Type;
+    use std::sync::Arc;
+
+    fn _batch() -> RecordBatch {
+        let color: DictionaryArray<Int8Type> = vec![Some("red"), None, Some("blue"), Some("red")]
+            .into_iter()
+            .collect();
+        RecordBatch::try_from_iter(vec![
+            (
+                "x",
+                Arc::new(Float32Array::from(vec![
+                    Some(0.5),
+                    None,
+                    Some(1.0),
+                    Some(2.0),
+                ])) as ArrayRef,
+            ),
+            ("color", Arc::new(color) as ArrayRef),
+            (
+                "query",
+                Arc::new(Int32Array::from(vec![7, 7, 3, 3])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d"])) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn features() {
+        let encoder = fit_categories(&_batch(), &["color", "x"]).unwrap();
+        assert_eq!(encoder.categorical_feature(), &[0]);
+        assert_eq!(encoder.categories(), &[vec!["red", "blue"]]);
+        let data = feature_matrix(&_batch(), &["color", "x"], Some(&encoder)).unwrap();
+        assert_eq!(&data[..1], &[0.0]);
+        assert!(data[1].is_nan());
+        assert_eq!(&data[2..5], &[1.0, 0.0, 0.5]);
+        assert!(data[5].is_nan());
+        assert_eq!(&data[6..], &[1.0, 2.0]);
+
+        assert!(feature_matrix(&_batch(), &["name"], None).is_err());
+        assert!(feature_matrix(&_batch(), &["y"], None).is_err());
+        assert!(feature_matrix(&_batch(), &["color"], None).is_err());
+        assert!(feature_matrix(&_batch(), &["x"], Some(&encoder)).is_err());
+    }
+
+    #[test]
+    fn dictionary_in_another_order() {
+        let encoder = fit_categories(&_batch(), &["color"]).unwrap();
+        // Keys 0, 1, 2 stand for blue, null and red; the last row has a null key.
+        let keys = Int8Array::from(vec![Some(2), Some(0), Some(1), None, Some(3)]);
+        let values = StringArray::from(vec![Some("blue"), None, Some("red"), Some("green")]);
+        let color = DictionaryArray::<Int8Type>::try_new(keys, Arc::new(values)).unwrap();
+        let batch =
+            RecordBatch::try_from_iter(vec![("color", Arc::new(color) as ArrayRef)]).unwrap();
+        let data = feature_matrix(&batch, &["color"], Some(&encoder)).unwrap();
+        assert_eq!(&data[..2], &[0.0, 1.0]);
+        assert!(data[2].is_nan());
+        assert!(data[3].is_nan());
+        assert!(data[4].is_nan());
+    }
+
+    #[test]
+    fn label_and_group() {
+        assert_eq!(f32_column(&_batch(), "query"), Ok(vec![7.0, 7.0, 3.0, 3.0]));
+        assert!(f32_column(&_batch(), "x").is_err());
+        assert_eq!(group_sizes(&_batch(), "query"), Ok(vec![2, 2]));
+        assert!(group_sizes(&_batch(), "x").is_err());
+
+        let batch = RecordBatch::try_from_iter(vec![(
+            "query",
+            Arc::new(Int32Array::from(vec![1, 2, 1])) as ArrayRef,
+        )])
+        .unwrap();
+        assert!(group_sizes(&batch, "query").is_err());
+    }
+}
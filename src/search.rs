@@ -0,0 +1,719 @@
+//! Hyperparameter search over a parameter space, scored on held-out or cross-validated metrics.
+
+use std::cmp::Ordering as CmpOrdering;
+use std::fmt::{self, Display};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use serde_json::{json, Map, Value};
+
+use crate::cv::is_higher_better;
+use crate::utils::SplitMix64;
+use crate::{cv, Booster, CvParameters, Dataset, Error, Result};
+
+/// Values a parameter takes in a [`ParameterSpace`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Distribution {
+    /// One of the given values; the only distribution of a grid search.
+    Choice(Vec<Value>),
+    /// A float drawn uniformly in `[low, high)`.
+    Uniform(f64, f64),
+    /// A float whose logarithm is drawn uniformly, for scales such as `learning_rate`.
+    LogUniform(f64, f64),
+    /// An integer drawn uniformly in `[low, high]`.
+    IntUniform(i64, i64),
+}
+
+impl Distribution {
+    fn validate(&self, name: &str) -> Result<()> {
+        let valid = match *self {
+            Distribution::Choice(ref values) => !values.is_empty(),
+            Distribution::Uniform(low, high) => low.is_finite() && high.is_finite() && low < high,
+            Distribution::LogUniform(low, high) => high.is_finite() && 0.0 < low && low < high,
+            Distribution::IntUniform(low, high) => low <= high,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::invalid_parameter(format!(
+                "invalid distribution of parameter {}: {:?}",
+                name, self
+            )))
+        }
+    }
+
+    fn sample(&self, rng: &mut SplitMix64) -> Value {
+        match *self {
+            Distribution::Choice(ref values) => values[rng.next_index(values.len())].clone(),
+            Distribution::Uniform(low, high) => json!(low + rng.next_f64() * (high - low)),
+            Distribution::LogUniform(low, high) => {
+                json!((low.ln() + rng.next_f64() * (high.ln() - low.ln())).exp())
+            }
+            Distribution::IntUniform(low, high) => {
+                json!(low + rng.next_index((high - low) as usize + 1) as i64)
+            }
+        }
+    }
+}
+
+/// The parameters to search and the values they take.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{Distribution, ParameterSpace};
+/// use serde_json::json;
+///
+/// let space = ParameterSpace::new()
+///     .with("num_leaves", Distribution::Choice(vec![json!(15), json!(31), json!(63)]))
+///     .with("learning_rate", Distribution::LogUniform(0.01, 0.3))
+///     .with("min_data_in_leaf", Distribution::IntUniform(5, 50));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParameterSpace {
+    parameters: Vec<(String, Distribution)>,
+}
+
+impl ParameterSpace {
+    /// Create an empty space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter, replacing any previous distribution of the same name.
+    pub fn with<S: Into<String>>(mut self, name: S, distribution: Distribution) -> Self {
+        let name = name.into();
+        self.parameters.retain(|(n, _)| *n != name);
+        self.parameters.push((name, distribution));
+        self
+    }
+
+    /// Every combination of the parameter choices.
+    fn grid(&self) -> Result<Vec<Map<String, Value>>> {
+        let mut grid = vec![Map::new()];
+        for (name, distribution) in &self.parameters {
+            distribution.validate(name)?;
+            let values = match *distribution {
+                Distribution::Choice(ref values) => values,
+                _ => {
+                    return Err(Error::invalid_parameter(format!(
+                        "grid search needs a choice of values for parameter {}",
+                        name
+                    )))
+                }
+            };
+            grid = grid
+                .into_iter()
+                .flat_map(|candidate| {
+                    values.iter().map(move |value| {
+                        let mut candidate = candidate.clone();
+                        candidate.insert(name.clone(), value.clone());
+                        candidate
+                    })
+                })
+                .collect();
+        }
+        Ok(grid)
+    }
+
+    fn sample(
+        &self,
+        num_candidates: usize,
+        rng: &mut SplitMix64,
+    ) -> Result<Vec<Map<String, Value>>> {
+        for (name, distribution) in &self.parameters {
+            distribution.validate(name)?;
+        }
+        Ok((0..num_candidates)
+            .map(|_| {
+                self.parameters
+                    .iter()
+                    .map(|(name, distribution)| (name.clone(), distribution.sample(rng)))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// How candidates of a [`search`] are scored.
+pub enum Evaluation {
+    /// Train on the whole dataset and score the first metric on a validation `Dataset` created
+    /// with the training data as reference.
+    Holdout(Arc<Dataset>),
+    /// Score the first metric of [`cv`](crate::cv()), at its best iteration.
+    CrossValidation(CvParameters),
+}
+
+/// How candidates of a [`search`] are chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// Every combination of the parameter choices.
+    Grid,
+    /// The given number of candidates drawn at random.
+    Random(usize),
+    /// Candidates drawn at random, trained with `min_iterations` iterations at first; after each
+    /// round only the best `1 / factor` of the candidates are kept and trained with `factor`
+    /// times more iterations, up to the `num_iterations` of the parameters.
+    SuccessiveHalving {
+        num_candidates: usize,
+        min_iterations: usize,
+        factor: usize,
+    },
+}
+
+/// Parameters of [`search`].
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct SearchParameters {
+    /// How candidates are chosen.
+    #[builder(default = "SearchStrategy::Grid")]
+    strategy: SearchStrategy,
+    /// Number of candidates trained at the same time.
+    #[builder(default = "1")]
+    num_parallel: usize,
+    /// Threads shared by the candidates trained at the same time, all available threads when 0.
+    /// Must stay 0 when the training parameters set `num_threads`.
+    #[builder(default = "0")]
+    num_threads: usize,
+    /// Seed of the random draws.
+    #[builder(default = "0")]
+    seed: u64,
+}
+
+impl Default for SearchParameters {
+    fn default() -> Self {
+        SearchParametersBuilder::default().build().unwrap()
+    }
+}
+
+/// A parameter combination evaluated by [`search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchCandidate {
+    parameters: Value,
+    score: f64,
+    num_iterations: usize,
+}
+
+impl SearchCandidate {
+    /// Values of the searched parameters.
+    pub fn parameters(&self) -> &Value {
+        &self.parameters
+    }
+
+    /// Score of the first metric.
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// Number of iterations the score was reached with.
+    pub fn num_iterations(&self) -> usize {
+        self.num_iterations
+    }
+}
+
+/// The candidates of a [`search`], ranked from best to worst.
+///
+/// Its `Display` implementation prints the ranking as a table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    eval_name: String,
+    candidates: Vec<SearchCandidate>,
+}
+
+impl SearchResult {
+    /// Name of the metric that ranks the candidates.
+    pub fn eval_name(&self) -> &str {
+        &self.eval_name
+    }
+
+    /// Candidates ranked from best to worst. With successive halving, candidates that reached
+    /// a later round rank first.
+    pub fn candidates(&self) -> &[SearchCandidate] {
+        &self.candidates
+    }
+
+    /// The best candidate.
+    pub fn best(&self) -> &SearchCandidate {
+        &self.candidates[0]
+    }
+}
+
+impl Display for SearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:>12}  {:>10}  parameters",
+            "rank", self.eval_name, "iterations"
+        )?;
+        for (rank, candidate) in self.candidates.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:>12.6}  {:>10}  {}",
+                rank + 1,
+                candidate.score,
+                candidate.num_iterations,
+                candidate.parameters
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Score of a candidate: the metric name, its value and the number of iterations.
+type Score = (String, f64, usize);
+
+fn evaluate(dataset: &Arc<Dataset>, parameter: &Value, evaluation: &Evaluation) -> Result<Score> {
+    let no_metric = || Error::invalid_parameter("search needs at least one metric");
+    match *evaluation {
+        Evaluation::Holdout(ref valid) => {
+            let mut booster = Booster::create_shared(Arc::clone(dataset), parameter)?;
+            booster.add_shared_valid_data(Arc::clone(valid))?;
+            let mut num_iterations = 0;
+            for _ in 0..Booster::num_iterations(parameter)?.max(0) {
+                if booster.update_one_iter()? {
+                    break;
+                }
+                num_iterations += 1;
+            }
+            let eval_name = booster
+                .eval_names()?
+                .into_iter()
+                .next()
+                .ok_or_else(no_metric)?;
+            Ok((eval_name, booster.eval(1)?[0], num_iterations))
+        }
+        Evaluation::CrossValidation(ref cv_params) => {
+            let result = cv(dataset, parameter, cv_params)?;
+            let eval_name = result.eval_names().first().ok_or_else(no_metric)?.clone();
+            let score = result.mean().last().map_or(f64::NAN, |mean| mean[0]);
+            Ok((eval_name, score, result.best_iteration()))
+        }
+    }
+}
+
+/// Evaluate the parameters of every candidate, `num_parallel` at a time.
+fn evaluate_all(
+    dataset: &Arc<Dataset>,
+    parameters: &[Value],
+    evaluation: &Evaluation,
+    num_parallel: usize,
+) -> Result<Vec<Score>> {
+    let next = AtomicUsize::new(0);
+    let mut scores = thread::scope(|scope| {
+        let workers = (0..num_parallel.min(parameters.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut scores = Vec::new();
+                    loop {
+                        let idx = next.fetch_add(1, Ordering::Relaxed);
+                        if idx >= parameters.len() {
+                            return scores;
+                        }
+                        scores.push((idx, evaluate(dataset, &parameters[idx], evaluation)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+    scores.sort_by_key(|(idx, _)| *idx);
+    scores.into_iter().map(|(_, score)| score).collect()
+}
+
+/// Names LightGBM accepts for `num_threads`.
+const NUM_THREADS_ALIASES: [&str; 5] =
+    ["num_threads", "num_thread", "nthread", "nthreads", "n_jobs"];
+
+/// Training parameters of a candidate, with its share of the threads unless the parameters
+/// already set them.
+fn candidate_params(
+    base: &Map<String, Value>,
+    candidate: &Map<String, Value>,
+    num_iterations: Option<usize>,
+    search_params: &SearchParameters,
+) -> Result<Value> {
+    let mut params = base.clone();
+    params.extend(candidate.clone());
+    let has_num_threads = NUM_THREADS_ALIASES
+        .iter()
+        .any(|name| params.contains_key(*name));
+    match (has_num_threads, search_params.num_threads) {
+        (false, num_threads) => {
+            let num_threads = match num_threads {
+                0 => thread::available_parallelism().map_or(1, |n| n.get()),
+                n => n,
+            };
+            let threads_per_candidate = (num_threads / search_params.num_parallel.max(1)).max(1);
+            params.insert(String::from("num_threads"), json!(threads_per_candidate));
+        }
+        (true, 0) => {}
+        (true, _) => {
+            return Err(Error::invalid_parameter(
+                "num_threads is set both in the training and in the search parameters",
+            ))
+        }
+    }
+    if let Some(num_iterations) = num_iterations {
+        params.insert(String::from("num_iterations"), json!(num_iterations));
+    }
+    Ok(Value::Object(params))
+}
+
+/// Search the parameter space for the training parameters with the best first metric.
+///
+/// Every candidate overrides the values of `parameter` with its own. Candidates are trained
+/// `num_parallel` at a time on the shared `dataset`, each with an equal share of `num_threads`
+/// threads.
+///
+/// A `num_threads` set in `parameter` or in a candidate (or one of its aliases) wins over the
+/// share of the available threads when [`SearchParameters`] leave `num_threads` at 0; setting it
+/// in both places is an error.
+///
+/// Example
+/// ```
+/// extern crate serde_json;
+/// use lightgbm::{search, Dataset, Distribution, Evaluation, ParameterSpace, SearchParametersBuilder, SearchStrategy};
+/// use serde_json::json;
+/// use std::sync::Arc;
+///
+/// let train = Dataset::from_file(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.train").unwrap();
+/// let valid = Dataset::from_file_with_reference(&"lightgbm-sys/lightgbm/examples/binary_classification/binary.test", &train).unwrap();
+/// let (train, valid) = (Arc::new(train), Arc::new(valid));
+/// let params = json!{{"num_iterations": 10, "objective": "binary", "metric": "auc"}};
+/// let space = ParameterSpace::new()
+///     .with("num_leaves", Distribution::Choice(vec![json!(7), json!(31)]))
+///     .with("learning_rate", Distribution::LogUniform(0.01, 0.3));
+/// let search_params = SearchParametersBuilder::default()
+///     .strategy(SearchStrategy::Random(4))
+///     .num_parallel(2)
+///     .build()
+///     .unwrap();
+/// let result = search(&train, &params, &space, &Evaluation::Holdout(valid), &search_params).unwrap();
+/// println!("{}", result);
+/// let best_params = result.best().parameters();
+/// ```
+pub fn search(
+    dataset: &Arc<Dataset>,
+    parameter: &Value,
+    space: &ParameterSpace,
+    evaluation: &Evaluation,
+    search_params: &SearchParameters,
+) -> Result<SearchResult> {
+    let base = parameter
+        .as_object()
+        .ok_or_else(|| Error::invalid_parameter("parameters must be a JSON object"))?;
+    let num_parallel = search_params.num_parallel.max(1);
+
+    let mut rng = SplitMix64::new(search_params.seed);
+    let candidates = match search_params.strategy {
+        SearchStrategy::Grid => space.grid()?,
+        SearchStrategy::Random(num_candidates) => space.sample(num_candidates, &mut rng)?,
+        SearchStrategy::SuccessiveHalving { num_candidates, .. } => {
+            space.sample(num_candidates, &mut rng)?
+        }
+    };
+    if candidates.is_empty() {
+        return Err(Error::invalid_parameter("no candidate to evaluate"));
+    }
+
+    // (round reached, score) of every candidate
+    let mut scores: Vec<Option<(usize, Score)>> = vec![None; candidates.len()];
+    match search_params.strategy {
+        SearchStrategy::Grid | SearchStrategy::Random(_) => {
+            let parameters = candidates
+                .iter()
+                .map(|candidate| candidate_params(base, candidate, None, search_params))
+                .collect::<Result<Vec<_>>>()?;
+            for (idx, score) in evaluate_all(dataset, &parameters, evaluation, num_parallel)?
+                .into_iter()
+                .enumerate()
+            {
+                scores[idx] = Some((0, score));
+            }
+        }
+        SearchStrategy::SuccessiveHalving {
+            min_iterations,
+            factor,
+            ..
+        } => {
+            if min_iterations == 0 || factor < 2 {
+                return Err(Error::invalid_parameter(
+                    "successive halving needs at least 1 iteration and a factor of at least 2",
+                ));
+            }
+            let max_iterations = Booster::num_iterations(parameter)?.max(1) as usize;
+            let mut num_iterations = min_iterations.min(max_iterations);
+            let mut survivors = (0..candidates.len()).collect::<Vec<_>>();
+            for round in 0.. {
+                let parameters = survivors
+                    .iter()
+                    .map(|&idx| {
+                        candidate_params(
+                            base,
+                            &candidates[idx],
+                            Some(num_iterations),
+                            search_params,
+                        )
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let round_scores = evaluate_all(dataset, &parameters, evaluation, num_parallel)?;
+                let higher_better = is_higher_better(&round_scores[0].0);
+                let mut ranked = survivors
+                    .iter()
+                    .cloned()
+                    .zip(round_scores)
+                    .collect::<Vec<_>>();
+                ranked.sort_by(|a, b| compare_scores((a.1).1, (b.1).1, higher_better));
+                for (idx, score) in &ranked {
+                    scores[*idx] = Some((round, score.clone()));
+                }
+                if ranked.len() == 1 || num_iterations >= max_iterations {
+                    break;
+                }
+                let num_survivors = ranked.len().div_ceil(factor);
+                survivors = ranked[..num_survivors]
+                    .iter()
+                    .map(|(idx, _)| *idx)
+                    .collect();
+                num_iterations = num_iterations.saturating_mul(factor).min(max_iterations);
+            }
+        }
+    }
+
+    let mut ranked = candidates
+        .into_iter()
+        .zip(scores)
+        .filter_map(|(candidate, score)| score.map(|score| (candidate, score)))
+        .collect::<Vec<_>>();
+    let eval_name = (ranked[0].1).1 .0.clone();
+    let higher_better = is_higher_better(&eval_name);
+    ranked.sort_by(|a, b| {
+        (b.1)
+            .0
+            .cmp(&(a.1).0)
+            .then_with(|| compare_scores(((a.1).1).1, ((b.1).1).1, higher_better))
+    });
+    Ok(SearchResult {
+        eval_name,
+        candidates: ranked
+            .into_iter()
+            .map(
+                |(candidate, (_, (_, score, num_iterations)))| SearchCandidate {
+                    parameters: Value::Object(candidate),
+                    score,
+                    num_iterations,
+                },
+            )
+            .collect(),
+    })
+}
+
+/// Order scores from best to worst, with `NaN` last.
+fn compare_scores(a: f64, b: f64, higher_better: bool) -> CmpOrdering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => CmpOrdering::Equal,
+        (true, false) => CmpOrdering::Greater,
+        (false, true) => CmpOrdering::Less,
+        (false, false) if higher_better => b.partial_cmp(&a).unwrap(),
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::tests::read_train_file;
+    use crate::CvParametersBuilder;
+
+    fn _space() -> ParameterSpace {
+        ParameterSpace::new()
+            .with(
+                "num_leaves",
+                Distribution::Choice(vec![json!(3), json!(15)]),
+            )
+            .with(
+                "learning_rate",
+                Distribution::Choice(vec![json!(0.05), json!(0.1), json!(0.2)]),
+            )
+    }
+
+    #[test]
+    fn grid() {
+        let grid = _space().grid().unwrap();
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[1]["num_leaves"], json!(3));
+        assert_eq!(grid[1]["learning_rate"], json!(0.1));
+
+        let space = _space().with("lambda_l2", Distribution::Uniform(0.0, 1.0));
+        assert!(space.grid().is_err());
+    }
+
+    #[test]
+    fn sample() {
+        let space = ParameterSpace::new()
+            .with("a", Distribution::Uniform(0.5, 1.0))
+            .with("b", Distribution::LogUniform(0.01, 0.1))
+            .with("c", Distribution::IntUniform(2, 4));
+        let mut rng = SplitMix64::new(7);
+        for candidate in space.sample(20, &mut rng).unwrap() {
+            let a = candidate["a"].as_f64().unwrap();
+            let b = candidate["b"].as_f64().unwrap();
+            let c = candidate["c"].as_i64().unwrap();
+            assert!((0.5..1.0).contains(&a));
+            assert!((0.01..0.1).contains(&b));
+            assert!((2..=4).contains(&c));
+        }
+        let space = space.with("a", Distribution::Uniform(1.0, 0.0));
+        assert!(space.sample(1, &mut rng).is_err());
+    }
+
+    #[test]
+    fn ranking() {
+        assert_eq!(compare_scores(0.9, 0.8, true), CmpOrdering::Less);
+        assert_eq!(compare_scores(0.9, 0.8, false), CmpOrdering::Greater);
+        assert_eq!(compare_scores(f64::NAN, 0.8, true), CmpOrdering::Greater);
+    }
+
+    #[test]
+    fn candidate_threads() {
+        let base = json!({"objective": "binary"}).as_object().unwrap().clone();
+        let candidate = json!({"num_leaves": 7}).as_object().unwrap().clone();
+        let search_params = SearchParametersBuilder::default()
+            .num_parallel(2)
+            .num_threads(6)
+            .build()
+            .unwrap();
+        let params = candidate_params(&base, &candidate, Some(5), &search_params).unwrap();
+        assert_eq!(
+            params,
+            json!({"objective": "binary", "num_leaves": 7, "num_threads": 3, "num_iterations": 5})
+        );
+
+        let with_threads = json!({"n_jobs": 2}).as_object().unwrap().clone();
+        let params =
+            candidate_params(&base, &with_threads, None, &SearchParameters::default()).unwrap();
+        assert_eq!(params, json!({"objective": "binary", "n_jobs": 2}));
+        assert!(candidate_params(&with_threads, &candidate, None, &search_params).is_err());
+    }
+
+    #[test]
+    fn grid_search_holdout() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        let params = json! {{"num_iterations": 3, "objective": "binary", "metric": "auc"}};
+        let search_params = SearchParametersBuilder::default()
+            .num_parallel(3)
+            .num_threads(3)
+            .build()
+            .unwrap();
+        let result = search(
+            &Arc::new(train),
+            &params,
+            &_space(),
+            &Evaluation::Holdout(Arc::new(valid)),
+            &search_params,
+        )
+        .unwrap();
+        assert_eq!(result.eval_name(), "auc");
+        assert_eq!(result.candidates().len(), 6);
+        assert!(result
+            .candidates()
+            .windows(2)
+            .all(|w| w[0].score() >= w[1].score()));
+        assert_eq!(result.best(), &result.candidates()[0]);
+        assert_eq!(result.to_string().lines().count(), 7);
+    }
+
+    #[test]
+    fn holdout_counts_iterations_run() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        // no leaf can hold this many rows, so training finishes before the first tree
+        let params = json! {{
+            "num_iterations": 5,
+            "objective": "binary",
+            "metric": "auc",
+            "min_data_in_leaf": 100000
+        }};
+        let space = ParameterSpace::new().with("num_leaves", Distribution::Choice(vec![json!(7)]));
+        let result = search(
+            &Arc::new(train),
+            &params,
+            &space,
+            &Evaluation::Holdout(Arc::new(valid)),
+            &SearchParameters::default(),
+        )
+        .unwrap();
+        assert_eq!(result.best().num_iterations(), 0);
+    }
+
+    #[test]
+    fn random_search_cv() {
+        let params =
+            json! {{"num_iterations": 3, "objective": "binary", "metric": "binary_logloss"}};
+        let cv_params = CvParametersBuilder::default().nfold(2).build().unwrap();
+        let search_params = SearchParametersBuilder::default()
+            .strategy(SearchStrategy::Random(3))
+            .num_parallel(2)
+            .build()
+            .unwrap();
+        let result = search(
+            &Arc::new(read_train_file().unwrap()),
+            &params,
+            &_space(),
+            &Evaluation::CrossValidation(cv_params),
+            &search_params,
+        )
+        .unwrap();
+        assert_eq!(result.candidates().len(), 3);
+        assert!(result
+            .candidates()
+            .windows(2)
+            .all(|w| w[0].score() <= w[1].score()));
+    }
+
+    #[test]
+    fn successive_halving() {
+        let train = read_train_file().unwrap();
+        let valid = Dataset::from_file_with_reference(
+            "lightgbm-sys/lightgbm/examples/binary_classification/binary.test",
+            &train,
+        )
+        .unwrap();
+        let params = json! {{"num_iterations": 8, "objective": "binary", "metric": "auc"}};
+        let search_params = SearchParametersBuilder::default()
+            .strategy(SearchStrategy::SuccessiveHalving {
+                num_candidates: 4,
+                min_iterations: 2,
+                factor: 2,
+            })
+            .build()
+            .unwrap();
+        let result = search(
+            &Arc::new(train),
+            &params,
+            &_space(),
+            &Evaluation::Holdout(Arc::new(valid)),
+            &search_params,
+        )
+        .unwrap();
+        let iterations = result
+            .candidates()
+            .iter()
+            .map(|c| c.num_iterations())
+            .collect::<Vec<_>>();
+        assert_eq!(iterations, vec![8, 4, 2, 2]);
+    }
+}
@@ -0,0 +1,380 @@
+//! Construction of a `Dataset` from rows pushed in chunks.
+
+use libc::{c_char, c_void};
+use std;
+use std::ffi::CString;
+
+use lightgbm_sys;
+
+use crate::matrix::check_categorical_value;
+use crate::{DType, Dataset, DatasetParameters, Error, MatrixView, Result};
+
+/// Values with a smaller magnitude are zeros for LightGBM and left out of the sample.
+const ZERO_THRESHOLD: f64 = 1e-35;
+
+/// A `Dataset` under construction, filled with rows pushed in chunks.
+///
+/// The bins of the features are built up front, either from a sample of rows or from a
+/// reference `Dataset`, so the rows never have to be held in memory at once. Rows are then
+/// pushed in order until the announced number of rows is reached, and
+/// [`DatasetStream::finish`] returns the `Dataset`.
+///
+/// Example
+/// ```
+/// use lightgbm::{DatasetParameters, DatasetStream, MatrixLayout, MatrixView};
+///
+/// let sample = vec![1.0, 0.1,
+///                   0.2, 0.8];
+/// let sample = MatrixView::new(&sample, 2, 2, MatrixLayout::RowMajor).unwrap();
+/// let mut stream = DatasetStream::from_sample(sample, 4, &DatasetParameters::default()).unwrap();
+///
+/// let chunk = vec![1.0, 0.1,
+///                  0.7, 0.4];
+/// let chunk = MatrixView::new(&chunk, 2, 2, MatrixLayout::RowMajor).unwrap();
+/// stream.push_rows(chunk, &[0.0, 0.0]).unwrap();
+/// let chunk = vec![0.2, 0.8,
+///                  0.1, 0.9];
+/// let chunk = MatrixView::new(&chunk, 2, 2, MatrixLayout::RowMajor).unwrap();
+/// stream.push_rows(chunk, &[1.0, 1.0]).unwrap();
+///
+/// let dataset = stream.finish().unwrap();
+/// assert_eq!(dataset.num_data(), Ok(4));
+/// ```
+pub struct DatasetStream {
+    dataset: Dataset,
+    num_feature: usize,
+    num_total_row: usize,
+    label: Vec<f32>,
+    categorical_idx: Vec<usize>,
+    feature_names: Vec<String>,
+}
+
+impl DatasetStream {
+    /// Create a stream of `num_total_row` rows whose feature bins are built from `sample`.
+    ///
+    /// The sample only serves to build the bins, its rows are not part of the `Dataset`
+    /// unless they are pushed as well.
+    pub fn from_sample<T: DType>(
+        sample: MatrixView<T>,
+        num_total_row: usize,
+        params: &DatasetParameters,
+    ) -> Result<Self> {
+        check_num_total_row(num_total_row)?;
+        let num_feature = sample.num_cols();
+        let (parameter, categorical_idx) = params.to_mat_string(num_feature)?;
+        sample.check_categorical(&categorical_idx)?;
+
+        let mut sample_values = vec![Vec::new(); num_feature];
+        let mut sample_indices = vec![Vec::new(); num_feature];
+        for row_idx in 0..sample.num_rows() {
+            for col in 0..num_feature {
+                let value = sample.get(row_idx, col);
+                if value.is_nan() || value.abs() > ZERO_THRESHOLD {
+                    sample_values[col].push(value);
+                    sample_indices[col].push(row_idx as i32);
+                }
+            }
+        }
+        let num_per_col = sample_values
+            .iter()
+            .map(|values| values.len() as i32)
+            .collect::<Vec<_>>();
+        let mut sample_values_ptr = sample_values
+            .iter_mut()
+            .map(|values| values.as_mut_ptr())
+            .collect::<Vec<_>>();
+        let mut sample_indices_ptr = sample_indices
+            .iter_mut()
+            .map(|indices| indices.as_mut_ptr())
+            .collect::<Vec<_>>();
+        let params_str = CString::new(parameter)?;
+        let mut handle = std::ptr::null_mut();
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetCreateFromSampledColumn(
+            sample_values_ptr.as_mut_ptr(),
+            sample_indices_ptr.as_mut_ptr(),
+            num_feature as i32,
+            num_per_col.as_ptr(),
+            sample.num_rows() as i32,
+            num_total_row as i32,
+            params_str.as_ptr() as *const c_char,
+            &mut handle
+        ))?;
+
+        Ok(Self {
+            dataset: Dataset::new(handle, None),
+            num_feature,
+            num_total_row,
+            label: Vec::with_capacity(num_total_row),
+            categorical_idx,
+            feature_names: params.feature_names().to_vec(),
+        })
+    }
+
+    /// Create a stream of `num_total_row` rows sharing the bin mappers of `reference`, as for
+    /// validation data.
+    pub fn from_reference(reference: &Dataset, num_total_row: usize) -> Result<Self> {
+        check_num_total_row(num_total_row)?;
+        let num_feature = reference.num_feature()? as usize;
+        let mut handle = std::ptr::null_mut();
+        lgbm_call!(lightgbm_sys::LGBM_DatasetCreateByReference(
+            reference.handle,
+            num_total_row as i64,
+            &mut handle
+        ))?;
+
+        Ok(Self {
+            dataset: Dataset::new(handle, Some(reference)),
+            num_feature,
+            num_total_row,
+            label: Vec::with_capacity(num_total_row),
+            categorical_idx: Vec::new(),
+            feature_names: Vec::new(),
+        })
+    }
+
+    /// Number of rows pushed so far.
+    pub fn num_pushed(&self) -> usize {
+        self.label.len()
+    }
+
+    /// Push the next dense rows with their labels.
+    pub fn push_rows<T: DType>(&mut self, rows: MatrixView<T>, label: &[f32]) -> Result<()> {
+        if rows.num_cols() != self.num_feature {
+            return Err(Error::invalid_input(format!(
+                "got rows of {} values for {} features",
+                rows.num_cols(),
+                self.num_feature
+            )));
+        }
+        self.check_push(rows.num_rows(), label)?;
+        rows.check_categorical(&self.categorical_idx)?;
+
+        // LightGBM only reads row-major chunks
+        let data = rows.row_major_values();
+        lgbm_call!(lightgbm_sys::LGBM_DatasetPushRows(
+            self.dataset.handle,
+            data.as_ptr() as *const c_void,
+            T::C_API_DTYPE,
+            rows.num_rows() as i32,
+            rows.num_cols() as i32,
+            self.label.len() as i32
+        ))?;
+        self.label.extend_from_slice(label);
+        Ok(())
+    }
+
+    /// Push the next sparse rows in CSR format with their labels.
+    ///
+    /// The values of row `i` are `data[indptr[i]..indptr[i + 1]]`, in the columns given by the
+    /// same range of `indices`.
+    pub fn push_csr<T: DType>(
+        &mut self,
+        indptr: &[i64],
+        indices: &[i32],
+        data: &[T],
+        label: &[f32],
+    ) -> Result<()> {
+        let num_rows = indptr.len().saturating_sub(1);
+        self.check_push(num_rows, label)?;
+        if indptr.first() != Some(&0)
+            || indptr.windows(2).any(|w| w[0] > w[1])
+            || indptr[num_rows] as usize != data.len()
+            || indices.len() != data.len()
+        {
+            return Err(Error::invalid_input(
+                "indptr must grow from 0 to the number of values and indices",
+            ));
+        }
+        if let Some(col) = indices
+            .iter()
+            .find(|&&col| col < 0 || col as usize >= self.num_feature)
+        {
+            return Err(Error::invalid_input(format!(
+                "column {} is out of range for {} features",
+                col, self.num_feature
+            )));
+        }
+        for (row_idx, range) in indptr.windows(2).enumerate() {
+            for k in range[0] as usize..range[1] as usize {
+                let col = indices[k] as usize;
+                if self.categorical_idx.contains(&col) {
+                    check_categorical_value(data[k].to_f64(), row_idx, col)?;
+                }
+            }
+        }
+
+        lgbm_call!(lightgbm_sys::LGBM_DatasetPushRowsByCSR(
+            self.dataset.handle,
+            indptr.as_ptr() as *const c_void,
+            lightgbm_sys::C_API_DTYPE_INT64 as i32,
+            indices.as_ptr(),
+            data.as_ptr() as *const c_void,
+            T::C_API_DTYPE,
+            indptr.len() as i64,
+            data.len() as i64,
+            self.num_feature as i64,
+            self.label.len() as i64
+        ))?;
+        self.label.extend_from_slice(label);
+        Ok(())
+    }
+
+    fn check_push(&self, num_rows: usize, label: &[f32]) -> Result<()> {
+        if label.len() != num_rows {
+            return Err(Error::invalid_input(format!(
+                "got {} labels for {} rows",
+                label.len(),
+                num_rows
+            )));
+        }
+        if num_rows == 0 {
+            return Err(Error::invalid_input("cannot push an empty chunk of rows"));
+        }
+        if self.label.len() + num_rows > self.num_total_row {
+            return Err(Error::invalid_input(format!(
+                "cannot push {} rows after {} of {} rows",
+                num_rows,
+                self.label.len(),
+                self.num_total_row
+            )));
+        }
+        Ok(())
+    }
+
+    /// Set the labels and feature names once every row has been pushed, and return the
+    /// `Dataset`.
+    ///
+    /// Weights and query groups can then be set with [`Dataset::set_weight`] and
+    /// [`Dataset::set_group`].
+    pub fn finish(mut self) -> Result<Dataset> {
+        if self.label.len() != self.num_total_row {
+            return Err(Error::invalid_input(format!(
+                "only {} of {} rows were pushed",
+                self.label.len(),
+                self.num_total_row
+            )));
+        }
+        self.dataset.set_label(&self.label)?;
+        if !self.feature_names.is_empty() {
+            self.dataset.set_feature_names(&self.feature_names)?;
+        }
+        Ok(self.dataset)
+    }
+}
+
+/// Check that LightGBM can index `num_total_row` rows.
+fn check_num_total_row(num_total_row: usize) -> Result<()> {
+    if num_total_row > i32::MAX as usize {
+        return Err(Error::invalid_input(format!(
+            "{} rows are too many for LightGBM",
+            num_total_row
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, DatasetParametersBuilder, MatrixLayout};
+
+    fn _rows() -> Vec<f64> {
+        vec![
+            1.0, 0.1, 0.0, //
+            0.7, 0.4, 1.0, //
+            0.9, 0.8, 2.0, //
+            0.2, 0.2, 1.0, //
+            0.1, 0.7, 0.0,
+        ]
+    }
+
+    fn _stream() -> DatasetStream {
+        let rows = _rows();
+        let sample = MatrixView::new(&rows, 5, 3, MatrixLayout::RowMajor).unwrap();
+        let params = DatasetParametersBuilder::default()
+            .feature_names(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .categorical_feature(vec![Column::from(2)])
+            .build()
+            .unwrap();
+        DatasetStream::from_sample(sample, 5, &params).unwrap()
+    }
+
+    #[test]
+    fn push_rows() {
+        let rows = _rows();
+        let mut stream = _stream();
+        let chunk = MatrixView::new(&rows[..6], 2, 3, MatrixLayout::RowMajor).unwrap();
+        stream.push_rows(chunk, &[0.0, 0.0]).unwrap();
+        let col_major = vec![0.9, 0.2, 0.1, 0.8, 0.2, 0.7, 2.0, 1.0, 0.0];
+        let chunk = MatrixView::new(&col_major, 3, 3, MatrixLayout::ColumnMajor).unwrap();
+        assert!(stream.push_rows(chunk, &[0.0]).is_err());
+        stream.push_rows(chunk, &[0.0, 1.0, 1.0]).unwrap();
+        assert_eq!(stream.num_pushed(), 5);
+        assert!(stream.push_rows(chunk, &[0.0, 1.0, 1.0]).is_err());
+
+        let dataset = stream.finish().unwrap();
+        assert_eq!(dataset.num_data(), Ok(5));
+        assert_eq!(dataset.num_feature(), Ok(3));
+        assert_eq!(
+            dataset.feature_names(),
+            Ok(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+    }
+
+    #[test]
+    fn push_invalid_rows() {
+        let mut stream = _stream();
+        let rows = vec![0.5, 0.5];
+        let chunk = MatrixView::new(&rows, 1, 2, MatrixLayout::RowMajor).unwrap();
+        assert!(stream.push_rows(chunk, &[0.0]).is_err());
+        let rows = vec![0.5, 0.5, 1.5];
+        let chunk = MatrixView::new(&rows, 1, 3, MatrixLayout::RowMajor).unwrap();
+        assert!(stream.push_rows(chunk, &[0.0]).is_err());
+        assert!(stream.finish().is_err());
+    }
+
+    #[test]
+    fn push_csr() {
+        let mut stream = _stream();
+        let indptr = vec![0_i64, 2, 3];
+        stream
+            .push_csr(&indptr, &[0, 1, 2], &[1.0_f32, 0.1, 1.0], &[0.0, 1.0])
+            .unwrap();
+        assert!(stream
+            .push_csr(&indptr, &[0, 1, 3], &[1.0_f32, 0.1, 1.0], &[0.0, 1.0])
+            .is_err());
+        assert!(stream
+            .push_csr(&[0, 2, 1], &[0, 1, 2], &[1.0_f32, 0.1, 1.0], &[0.0, 1.0])
+            .is_err());
+        assert!(stream
+            .push_csr(&indptr, &[0, 1, 2], &[1.0_f32, 0.1, 1.5], &[0.0, 1.0])
+            .is_err());
+        assert!(stream
+            .push_csr(&indptr, &[0, 1, 2], &[1.0_f32, 0.1, -1.0], &[0.0, 1.0])
+            .is_err());
+        assert_eq!(stream.num_pushed(), 2);
+        stream
+            .push_csr(&[0, 1, 1, 2], &[1, 0], &[0.5_f64, 0.2], &[0.0, 1.0, 1.0])
+            .unwrap();
+        assert_eq!(stream.finish().unwrap().num_data(), Ok(5));
+    }
+
+    #[test]
+    fn from_reference() {
+        let rows = _rows();
+        let train = Dataset::from_slice(
+            MatrixView::new(&rows, 5, 3, MatrixLayout::RowMajor).unwrap(),
+            &[0.0, 0.0, 0.0, 1.0, 1.0],
+        )
+        .unwrap();
+        let mut stream = DatasetStream::from_reference(&train, 2).unwrap();
+        let chunk = MatrixView::new(&rows[..6], 2, 3, MatrixLayout::RowMajor).unwrap();
+        stream.push_rows(chunk, &[0.0, 1.0]).unwrap();
+        let valid = stream.finish().unwrap();
+        assert_eq!(valid.num_data(), Ok(2));
+        assert!(valid.shares_bin_mappers_with(&train));
+
+        assert!(DatasetStream::from_reference(&train, i32::MAX as usize + 1).is_err());
+    }
+}
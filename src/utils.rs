@@ -0,0 +1,154 @@
+//! Small helpers shared by the FFI wrappers.
+
+use libc::c_char;
+#[cfg(any(feature = "arrow", feature = "dataframe"))]
+use std::collections::HashSet;
+use std::ffi::CStr;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use serde_json::Value;
+
+use crate::{Error, Result};
+
+/// Read a list of strings from a LightGBM getter that follows the two-pass sizing protocol
+/// (`LGBM_BoosterGetFeatureNames`, `LGBM_DatasetGetFeatureNames`, ...).
+///
+/// `getter` is called with `(len, &mut out_len, buffer_len, &mut out_buffer_len, out_strs)`.
+/// The first call only asks for the number of strings and the required buffer size, the second
+/// call fills buffers that are large enough for every string.
+pub(crate) fn read_string_array<F>(mut getter: F) -> Result<Vec<String>>
+where
+    F: FnMut(i32, &mut i32, usize, &mut usize, *mut *mut c_char) -> Result<()>,
+{
+    let mut num_strings = 0;
+    let mut buffer_len = 0;
+    getter(
+        0,
+        &mut num_strings,
+        0,
+        &mut buffer_len,
+        std::ptr::null_mut(),
+    )?;
+
+    let mut buffers = (0..num_strings)
+        .map(|_| vec![0_u8; buffer_len])
+        .collect::<Vec<_>>();
+    let mut out_strs = buffers
+        .iter_mut()
+        .map(|b| b.as_mut_ptr() as *mut c_char)
+        .collect::<Vec<_>>();
+    let mut out_num_strings = 0;
+    let mut out_buffer_len = 0;
+    getter(
+        num_strings,
+        &mut out_num_strings,
+        buffer_len,
+        &mut out_buffer_len,
+        out_strs.as_mut_ptr(),
+    )?;
+    if out_num_strings != num_strings || out_buffer_len > buffer_len {
+        return Err(Error::invalid_input(
+            "string list changed between sizing and reading calls",
+        ));
+    }
+
+    buffers
+        .iter()
+        .map(|b| {
+            let c_str = unsafe { CStr::from_ptr(b.as_ptr() as *const c_char) };
+            Ok(c_str.to_str()?.to_string())
+        })
+        .collect()
+}
+
+/// Append a `key` line holding a JSON value to a model file written by `Booster::save_file`.
+///
+/// LightGBM ignores the extra line when loading the model.
+pub(crate) fn append_model_line(filename: &str, key: &str, value: &Value) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(filename)
+        .map_err(|e| Error::io(filename, e))?;
+    writeln!(file, "\n{}{}", key, value).map_err(|e| Error::io(filename, e))
+}
+
+/// Read the JSON value of the last `key` line of a model file, if there is one.
+pub(crate) fn read_model_line(filename: &str, key: &str) -> Result<Option<Value>> {
+    let model = fs::read_to_string(filename).map_err(|e| Error::io(filename, e))?;
+    model
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix(key))
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| Error::invalid_input(format!("invalid model line {}: {}", key, e)))
+        })
+        .transpose()
+}
+
+/// Convert the query id of every row to the sizes of consecutive groups expected by LightGBM.
+///
+/// Rows of a query must be contiguous, and a null id is an error.
+#[cfg(any(feature = "arrow", feature = "dataframe"))]
+pub(crate) fn group_sizes_from_ids<I>(ids: I, column: &str) -> Result<Vec<i32>>
+where
+    I: IntoIterator<Item = Option<i64>>,
+{
+    let mut sizes = Vec::new();
+    let mut seen = HashSet::new();
+    let mut current = None;
+    for (row_idx, id) in ids.into_iter().enumerate() {
+        let id = id.ok_or_else(|| {
+            Error::invalid_input(format!("null in row {} of column '{}'", row_idx, column))
+        })?;
+        if current == Some(id) {
+            *sizes.last_mut().unwrap() += 1;
+            continue;
+        }
+        if !seen.insert(id) {
+            return Err(Error::invalid_input(format!(
+                "rows of group {} in column '{}' are not contiguous",
+                id, column
+            )));
+        }
+        current = Some(id);
+        sizes.push(1);
+    }
+    Ok(sizes)
+}
+
+/// Small deterministic random number generator (SplitMix64), enough to shuffle and sample
+/// rows and parameters reproducibly from a seed.
+pub(crate) struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Uniform index in `0..n`, for a positive `n`.
+    pub(crate) fn next_index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Shuffle `items` in place (Fisher-Yates).
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.next_index(i + 1));
+        }
+    }
+}